@@ -0,0 +1,78 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use lonely_tribes_components::{
+    occupancy::TileGrid, point_light::PointLight, tile_transform::TileTransform,
+};
+use lonely_tribes_fog_of_war::fog::LightCacher;
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+
+fn get_lights() -> Vec<(TileTransform, PointLight)> {
+    (0..8)
+        .map(|i| {
+            (
+                TileTransform::new(4 + i * 7, 6 + (i % 3) * 10),
+                PointLight::for_tribe(6, i as usize),
+            )
+        })
+        .collect()
+}
+
+fn get_colls() -> TileGrid<bool> {
+    let mut colls = Vec::new();
+    for x in 0..WIDTH {
+        colls.push(TileTransform::new(x, 0));
+        colls.push(TileTransform::new(x, HEIGHT - 1));
+        if x % 5 == 0 {
+            for y in 10..20 {
+                colls.push(TileTransform::new(x, y));
+            }
+        }
+    }
+    TileGrid::from_tiles(&colls)
+}
+
+pub fn full_rebuild_benchmark(c: &mut Criterion) {
+    let lights = get_lights();
+    let colls = get_colls();
+
+    c.bench_function("fog full rebuild", |b| {
+        b.iter(|| LightCacher::default().update(&lights, &colls, true))
+    });
+}
+
+pub fn single_move_benchmark(c: &mut Criterion) {
+    let colls = get_colls();
+    let lights = get_lights();
+    let mut moved = lights.clone();
+    moved[0].0 += TileTransform::new(1, 0);
+
+    let mut cacher = LightCacher::default();
+    cacher.update(&lights, &colls, true);
+    let mut flip = false;
+
+    c.bench_function("fog single light moved", |b| {
+        b.iter(|| {
+            flip = !flip;
+            cacher.update(if flip { &moved } else { &lights }, &colls, true)
+        })
+    });
+}
+
+pub fn unchanged_benchmark(c: &mut Criterion) {
+    let colls = get_colls();
+    let lights = get_lights();
+
+    let mut cacher = LightCacher::default();
+    cacher.update(&lights, &colls, true);
+
+    c.bench_function("fog nothing changed", |b| {
+        b.iter(|| cacher.update(&lights, &colls, true))
+    });
+}
+
+criterion_group!(
+    fog,
+    full_rebuild_benchmark,
+    single_move_benchmark,
+    unchanged_benchmark
+);
+criterion_main!(fog);
@@ -1,7 +1,7 @@
 mod states;
 
 use amethyst::{
-    audio::{AudioBundle, DjSystemDesc},
+    audio::AudioBundle,
     core::transform::TransformBundle,
     input::{InputBundle, StringBindings},
     prelude::*,
@@ -17,28 +17,39 @@ use amethyst::{
     LoggerConfig,
 };
 use lonely_tribes_lib::{
-    audio::Muzac,
+    config::LTConfig,
     paths::{get_directory, is_end_user_build},
-    CONFIG,
 };
 use lonely_tribes_systems::{
+    achievements::AchievementSystem,
+    boss::{BossHpUiSystem, BossSystem},
+    budget_meter::{BudgetMeterSystem, ModeFxSystem},
     colliders_list_system::ListSystem,
     fog_of_war::{FogOfWarSystem, LightListSystem},
     fps_counter::FpsPrinterSystem,
+    hazards::HazardSystem,
+    hud::HudSystem,
     message_system::MessageSystem,
-    move_player::MovePlayerSystem,
+    move_player::{MovePlayerSystem, MovementType},
+    music_director::MusicDirectorSystem,
+    npcs::NpcSystem,
+    platform_manager::PlatformManager,
     player_overlap_checker::PlayerOverlapChecker,
-    steamworks_manager::SteamworksManager,
+    screen_scaling::{ScreenDimensionsSystem, ScreenScalingSystem},
+    script::ScriptSystem,
+    sfx::{GameEventSfxSystem, SfxSystem, UiSfxSystem},
+    switches::SwitchSystem,
     tint_animator::TintAnimatorSystem,
+    tribe_selector::TribeSelectorSystem,
     txt_wobble_system::TextWobbleSystem,
     uitext_animator::UiTextAnimator,
-    update_score::ScoreUpdaterSystem,
     update_tile_transforms::UpdateTileTransforms,
 };
 use states::{help_state::HelpState, welcome_state::StartGameState};
+use std::path::PathBuf;
 
 fn main() -> amethyst::Result<()> {
-    let opts = *CONFIG;
+    let opts = LTConfig::new();
 
     let app_root = application_root_dir()?;
 
@@ -87,20 +98,44 @@ fn main() -> amethyst::Result<()> {
         )?
         .with(UpdateTileTransforms, "update_tile_transforms", &[])
         .with(ListSystem, "collider_list", &[])
+        .with(TribeSelectorSystem::default(), "tribe_selector", &[])
         .with(
             MovePlayerSystem::default(),
             "move_player",
-            &["collider_list", "update_tile_transforms"],
+            &["collider_list", "update_tile_transforms", "tribe_selector"],
         )
-        .with(PlayerOverlapChecker, "player_overlap", &[])
+        .with(NpcSystem::default(), "npcs", &["move_player"])
+        .with(HazardSystem, "hazards", &["move_player", "npcs"])
+        .with(BossSystem::default(), "boss", &["hazards"])
+        .with(BossHpUiSystem, "boss_hp_ui", &["boss"])
+        .with(PlayerOverlapChecker, "player_overlap", &["hazards", "boss"])
+        .with(SwitchSystem::default(), "switches", &["move_player"])
         .with(TextWobbleSystem, "txt_wobble", &[])
-        .with(ScoreUpdaterSystem, "score_updater", &[])
+        .with(HudSystem, "hud", &[])
         .with(LightListSystem, "light_list", &[])
         .with(FogOfWarSystem::default(), "fog_of_war", &["light_list"])
         .with(TintAnimatorSystem, "tint_animtor", &[])
+        .with(ModeFxSystem, "mode_fx", &["tint_animtor", "fog_of_war"])
         .with(UiTextAnimator, "uitext_animator", &[])
-        .with(MessageSystem::default(), "message_list", &[])
-        .with(SteamworksManager, "steamy_boi", &[]);
+        .with(
+            ScriptSystem::default(),
+            "script",
+            &["move_player", "player_overlap", "boss"],
+        )
+        .with(MessageSystem::default(), "message_list", &["script"])
+        .with(PlatformManager, "platform_manager", &[])
+        .with(
+            AchievementSystem::default(),
+            "achievements",
+            &["move_player", "player_overlap"],
+        )
+        .with(ScreenDimensionsSystem::default(), "screen_dimensions", &[])
+        .with(
+            ScreenScalingSystem::default(),
+            "screen_scaling",
+            &["screen_dimensions", "message_list", "txt_wobble"],
+        )
+        .with(BudgetMeterSystem, "budget_meter", &["screen_scaling"]);
 
     if opts.flags.fps {
         game_data = game_data.with(FpsCounterSystem, "fps", &[]).with(
@@ -109,19 +144,24 @@ fn main() -> amethyst::Result<()> {
             &["fps"],
         );
     }
+    if opts.conf.vol > 0.0 || opts.conf.sfx_vol > 0.0 {
+        game_data = game_data.with_bundle(AudioBundle::default())?;
+    }
     if opts.conf.vol > 0.0 {
-        game_data = game_data
-            .with_bundle(AudioBundle::default())?
-            .with_system_desc(
-                DjSystemDesc::new(|music: &mut Muzac| music.music.next()),
-                "dj_system",
-                &[],
-            );
+        game_data = game_data.with(MusicDirectorSystem::default(), "music_director", &[]);
     }
+    game_data = game_data
+        .with(UiSfxSystem::default(), "ui_sfx", &[])
+        .with(
+            GameEventSfxSystem::default(),
+            "game_event_sfx",
+            &["move_player", "player_overlap", "switches"],
+        )
+        .with(SfxSystem, "sfx", &["ui_sfx", "game_event_sfx"]);
 
     let mut game = {
         if std::fs::read_dir(get_directory(false)).is_ok() {
-            Application::new(resources, StartGameState::default(), game_data)?
+            build_game(resources, StartGameState::default(), game_data, opts)?
         } else {
             let p = get_directory(false);
             std::fs::create_dir(p.clone()).unwrap_or_else(|err| {
@@ -131,7 +171,7 @@ fn main() -> amethyst::Result<()> {
                     p.to_str().unwrap_or_default()
                 )
             });
-            Application::new(resources, HelpState, game_data)?
+            build_game(resources, HelpState, game_data, opts)?
         }
     };
     game.run();
@@ -139,6 +179,19 @@ fn main() -> amethyst::Result<()> {
     Ok(())
 }
 
+///Builds the application, with the resources that need to exist before any systems are set up
+fn build_game<'a, S: State<GameData<'a, 'a>, StateEvent> + 'a>(
+    resources: PathBuf,
+    initial_state: S,
+    game_data: GameDataBuilder<'a, 'a>,
+    opts: LTConfig,
+) -> amethyst::Result<Application<'a, GameData<'a, 'a>>> {
+    Application::build(resources, initial_state)?
+        .with_resource(opts)
+        .with_resource(MovementType::new(opts.flags))
+        .build(game_data)
+}
+
 pub fn get_colours(r: f32, g: f32, b: f32) -> [f32; 4] {
     let (r, g, b, a) = Srgba::new(r / 255.0, g / 255.0, b / 255.0, 1.0)
         .into_linear()
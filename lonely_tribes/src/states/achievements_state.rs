@@ -0,0 +1,127 @@
+use super::welcome_state::StartGameState;
+use amethyst::{
+    core::ecs::{Builder, World, WorldExt},
+    input::{InputEvent, VirtualKeyCode},
+    ui::{Anchor, LineMode, UiText, UiTransform},
+    GameData, SimpleState, SimpleTrans, StateData, StateEvent,
+};
+use lonely_tribes_components::screen_scaled::ScreenScaled;
+use lonely_tribes_lib::{
+    achievements::{Achievement, Achievements},
+    audio::{set_music_context, MusicContext},
+    states_util::{get_scaling_factor, load_font},
+};
+
+///Colour for achievements which haven't been unlocked yet
+pub const LOCKED_COLOUR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
+///State to show all of the achievements, and which ones have been unlocked
+#[derive(Default)]
+pub struct AchievementsState;
+
+impl SimpleState for AchievementsState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        world.delete_all();
+
+        set_music_context(world, MusicContext::Menu);
+        get_achievements_txt(world);
+    }
+
+    fn handle_event(
+        &mut self,
+        _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        let mut t = SimpleTrans::None;
+        if let StateEvent::Input(InputEvent::KeyPressed { key_code, .. }) = event {
+            use VirtualKeyCode::*;
+            if key_code == Space || key_code == Return || key_code == Escape {
+                t = SimpleTrans::Switch(Box::new(StartGameState::default()));
+            }
+        }
+
+        t
+    }
+}
+
+///Function to insert the achievements text onto the screen
+///The text is **not** interactable.
+fn get_achievements_txt(world: &mut World) {
+    let (sf_x, sf_y) = get_scaling_factor(world);
+    let bold_font_handle = load_font(world, "ZxSpectrumBold");
+    let font_handle = load_font(world, "ZxSpectrum");
+
+    let (lines, stats_line) = {
+        let achievements = world.read_resource::<Achievements>();
+        let lines = Achievement::ALL
+            .iter()
+            .map(|a| {
+                let unlocked = achievements.is_unlocked(*a);
+                (
+                    format!(
+                        "[{}] {} - {}",
+                        if unlocked { "X" } else { " " },
+                        a.name(),
+                        a.description()
+                    ),
+                    unlocked,
+                )
+            })
+            .collect::<Vec<_>>();
+        let stats = &achievements.stats;
+        let stats_line = format!(
+            "Levels won: {}, Tribe members merged: {}, Procedural seeds beaten: {}",
+            stats.levels_won,
+            stats.tribes_merged,
+            stats.seeds_beaten.len()
+        );
+
+        (lines, stats_line)
+    };
+
+    let add_txt = |world: &mut World, y: f32, text: String, colour: [f32; 4], bold: bool| {
+        let trans = UiTransform::new(
+            format!("achievement_txt_{}", y),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.0,
+            sf_y * y,
+            0.5,
+            sf_x * 1500.0,
+            sf_y * 60.0,
+        );
+        let txt = UiText::new(
+            if bold {
+                bold_font_handle.clone()
+            } else {
+                font_handle.clone()
+            },
+            text,
+            colour,
+            sf_y * if bold { 60.0 } else { 30.0 },
+            LineMode::Wrap,
+            Anchor::Middle,
+        );
+        world
+            .create_entity()
+            .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
+            .with(trans)
+            .with(txt)
+            .build();
+    };
+
+    add_txt(world, 350.0, "Achievements".to_string(), [1.0; 4], true);
+    for (i, (line, unlocked)) in lines.into_iter().enumerate() {
+        let colour = if unlocked { [1.0; 4] } else { LOCKED_COLOUR };
+        add_txt(world, 200.0 - i as f32 * 80.0, line, colour, false);
+    }
+    add_txt(world, -250.0, stats_line, [1.0; 4], false);
+    add_txt(
+        world,
+        -350.0,
+        "Press [Space] or [Return] to go back.".to_string(),
+        [1.0; 4],
+        false,
+    );
+}
@@ -5,13 +5,17 @@ use amethyst::{
     ui::{Anchor, LineMode, UiText, UiTransform},
     GameData, SimpleState, SimpleTrans, StateData, StateEvent, Trans,
 };
-use lonely_tribes_components::win_related::{GameState, GameStateEnum};
+use lonely_tribes_components::{
+    screen_scaled::ScreenScaled,
+    win_related::{GameState, GameStateEnum},
+};
 use lonely_tribes_generation::level::Level;
 use lonely_tribes_lib::{
+    audio::{set_music_context, MusicContext},
+    config::LTConfig,
     either::Either,
     high_scores::HighScores,
     states_util::{get_scaling_factor, levels_len, load_font},
-    CONFIG,
 };
 use std::collections::HashMap;
 
@@ -33,20 +37,17 @@ impl PostGameState {
 impl SimpleState for PostGameState {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;
+        set_music_context(world, MusicContext::Menu);
 
         let (level_from, is_last_level, won, score) = get_stuff(world);
         let mut high_score = HighScores::new();
 
-        let opts = CONFIG.flags;
+        let opts = world.read_resource::<LTConfig>().flags;
 
         let mut nu_high_score = None;
 
         if !opts.debug && won && level_from.contains("lvl-") {
             nu_high_score = Some(high_score.add_score_and_write(level_from.clone(), score));
-
-            // let steam = world.write_resource::<SteamworksHolder>();
-            // let client = &steam.0;
-            //TODO: Work out how to do stats
         }
 
         let won_txt = if won && level_from.contains("lvl-") {
@@ -153,7 +154,7 @@ pub fn get_stuff(world: &World) -> (String, bool, bool, i32) {
 ///
 ///By default, it uses a non-bold sans-serif font called ZxSpectrum
 pub fn get_end_txt(world: &mut World, won_txt: String) {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let trans = UiTransform::new(
         "won_txt".to_string(),
         Anchor::Middle,
@@ -172,5 +173,10 @@ pub fn get_end_txt(world: &mut World, won_txt: String) {
         LineMode::Wrap,
         Anchor::Middle,
     );
-    world.create_entity().with(trans).with(txt).build();
+    world
+        .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
+        .with(trans)
+        .with(txt)
+        .build();
 }
@@ -13,31 +13,46 @@ use amethyst::{
     input::{InputEvent, VirtualKeyCode},
     prelude::*,
     renderer::{palette::Srgba, resources::Tint, SpriteRender, SpriteSheet},
-    ui::{Anchor, Interactable, LineMode, UiText, UiTransform},
+    ui::{Anchor, LineMode, UiImage, UiText, UiTransform},
     winit::{Event, WindowEvent},
 };
 use lonely_tribes_animations::{
     animation::Animator, interpolation::AnimInterpolation, movement::MovementAnimationData,
-    rotation::RotationAnimationData, tint::TintAnimatorData,
+    rotation::RotationAnimationData, scale::ScaleAnimationData, tint::TintAnimatorData,
 };
 use lonely_tribes_components::{
-    colliders::{Collider, ColliderList},
+    boss::{Boss, BossConfig, BossHpText, RespawnPoint},
+    budget_meter::{BudgetMeter, ModeFx},
+    colliders::{Collider, Pushable},
     data_holder::EntityHolder,
+    events::{GameEvent, GameEventChannel},
+    floors::{Floor, FloorEffect},
+    hazards::Hazard,
+    hud::{best_and_par_text, HudElement, HudText},
+    npcs::{Npc, NpcPattern},
+    occupancy::OccupancyGrid,
+    player::TribeControl,
     point_light::{PointLight, TintOverride},
-    score::Score,
+    screen_scaled::ScreenScaled,
+    script::{LevelScript, ScriptEntry},
+    switches::Linked,
     tile_transform::TileTransform,
     win_related::{GameModeManager, GamePlayingMode, GameState, GameStateEnum},
 };
+use lonely_tribes_fog_of_war::explored::ExploredTiles;
 use lonely_tribes_generation::{
     level::{Level, ReadInLevel, RT_PROCGEN_FILENAME},
-    sprite_stuff::{FromSpr, Room},
+    sprite_stuff::{FromSpr, Room, SpriteRequest},
 };
 use lonely_tribes_lib::{
+    audio::{set_music_context, MusicContext, Muzac},
+    config::LTConfig,
     either::Either,
+    high_scores::HighScores,
     paths::get_directory,
     states_util::{
-        get_levels_str, get_scaling_factor, init_camera, load_font, load_sprite_sheet,
-        CAMERA_DIMENSIONS,
+        get_camera_dimensions, get_grid_offset, get_levels_str, get_scaling_factor, init_camera,
+        load_font, load_sprite_sheet,
     },
 };
 use lonely_tribes_systems::{
@@ -56,8 +71,8 @@ pub struct PuzzleState {
     level_path: String,
     ///Holding a HashMap of which keys lead to which indicies of *LEVELS*
     actions: HashMap<VirtualKeyCode, String>,
-    ///Option variable to hold the Score text
-    score_button: Option<Entity>,
+    ///Vec to hold every entity in the HUD, so they can be hidden
+    hud_entities: Vec<Entity>,
     ///Vec to hold entities for temporary mode effects (eg. nudger)
     tmp_fx_entities: Vec<Entity>,
     ///timer for when we lose containing (so far, duration, entity)
@@ -70,7 +85,7 @@ impl Default for PuzzleState {
             ws: GameStateEnum::default(),
             level_path: "not a path".to_string(),
             actions: HashMap::new(),
-            score_button: None,
+            hud_entities: Vec::new(),
             tmp_fx_entities: Vec::new(),
             death_timer: None,
             seed_opt: None,
@@ -99,6 +114,21 @@ impl PuzzleState {
                 .unwrap_or_else(|err| log::warn!("Unable to delete tmp fx entitity: {}", err));
         }
     }
+
+    ///Hides or shows every entity in the HUD
+    pub fn set_hud_hidden(&self, world: &mut World, hidden: bool) {
+        let mut hiddens = world.write_storage::<Hidden>();
+        for e in &self.hud_entities {
+            if hidden {
+                hiddens.insert(*e, Hidden).unwrap_or_else(|err| {
+                    log::warn!("Unable to hide HUD entity: {}", err);
+                    None
+                });
+            } else {
+                hiddens.remove(*e);
+            }
+        }
+    }
 }
 
 impl SimpleState for PuzzleState {
@@ -106,35 +136,75 @@ impl SimpleState for PuzzleState {
         let world = data.world;
         world.delete_all();
 
-        init_camera(world, *CAMERA_DIMENSIONS);
+        let camera_dimensions = get_camera_dimensions(&world.read_resource::<LTConfig>().conf);
+        init_camera(world, camera_dimensions);
 
         let handle = load_sprite_sheet(world, "colored_tilemap_packed");
 
         let (room, seed_opt) = Level::new(&self.level_path);
-        let holder = load_level(world, handle, room.room.clone());
+        let mut holder = load_level(world, handle.clone(), room.room.clone());
+        load_npcs(world, handle.clone(), &room.npcs, &mut holder);
+        let mut messages = room.messages.clone();
+        if let Some(seed) = seed_opt {
+            messages.push((1.0, format!("Enjoy playing on seed: {}", seed)));
+        }
+        let mut script = ScriptEntry::from_messages(&messages);
+        script.extend(room.script.iter().cloned());
+        world.insert(LevelScript::new(
+            script,
+            SpriteRender::new(handle.clone(), SpriteRequest::Highlight as i32 as usize),
+        ));
+        if let Some(boss) = &room.boss {
+            load_boss(world, handle, boss, &mut holder);
+        }
         self.seed_opt = seed_opt;
 
         world.insert(GameState::new(None, self.level_path.clone(), 0));
+        //saved procgen levels have their seed in the file, rather than from Level::new
+        let seed = seed_opt.or_else(|| match Level::get_seed_index_from_path(&self.level_path) {
+            Either::Two(seed) if self.level_path.contains("pg-") => Some(seed),
+            _ => None,
+        });
+        world
+            .write_resource::<GameEventChannel>()
+            .single_write(GameEvent::LevelStarted {
+                level: self.level_path.clone(),
+                seed,
+            });
 
         world.insert(holder);
-        world.insert(GameModeManager::new(room.specials as i32));
+        world.insert(GameModeManager::with_rules(
+            room.specials as i32,
+            room.allowed_modes.clone(),
+            &room.mode_budgets,
+        ));
         world.insert(MovementDisabler { enabled: false });
+        world.insert(ExploredTiles::new(room.fog_memory));
+        world.insert(TribeControl::new(room.independent_tribes));
+
+        world
+            .entry::<Muzac>()
+            .or_insert_with(Muzac::default)
+            .set_level_track(room.music.clone());
+        set_music_context(world, MusicContext::Puzzle);
 
         self.actions
             .insert(VirtualKeyCode::R, self.level_path.clone());
 
-        self.score_button = Some(add_score(world));
-
-        {
-            let mut timed_msg_list = world.write_resource::<TimedMessagesToAdd>();
-            timed_msg_list.timer = 0.0;
-            timed_msg_list.list = room.messages;
-
-            if let Some(seed) = seed_opt {
-                timed_msg_list
-                    .list
-                    .push((1.0, format!("Enjoy playing on seed: {}", seed)));
+        let best = match Level::get_seed_index_from_path(&self.level_path) {
+            Either::One(index) if self.level_path.contains("lvl-") => {
+                HighScores::new().get_high_score(index)
             }
+            _ => None,
+        };
+        self.hud_entities = add_hud(
+            world,
+            room.display_name(&self.level_path, seed),
+            best_and_par_text(best, room.par),
+        );
+        if room.boss.is_some() {
+            let hp = add_boss_hp(world);
+            self.hud_entities.push(hp);
         }
     }
 
@@ -147,6 +217,7 @@ impl SimpleState for PuzzleState {
             world.write_resource::<TimedMessagesToAdd>().list.clear();
             world.write_resource::<MessageList>().0.clear();
         }
+        world.insert(LevelScript::default());
 
         if let GameStateEnum::End { lost_position } = self.ws {
             world.insert(GameState::new(
@@ -158,9 +229,7 @@ impl SimpleState for PuzzleState {
     }
 
     fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
-        if let Some(btn) = self.score_button {
-            data.world.write_storage::<Hidden>().remove(btn);
-        }
+        self.set_hud_hidden(data.world, false);
     }
 
     fn handle_event(
@@ -175,50 +244,42 @@ impl SimpleState for PuzzleState {
         match event {
             StateEvent::Input(InputEvent::KeyPressed { key_code, .. }) => match key_code {
                 Space => {
-                    if let Some(btn) = self.score_button {
-                        let mut hiddens = world.write_storage::<Hidden>();
-                        if hiddens.contains(btn) {
-                            hiddens.remove(btn);
-                        } else {
-                            hiddens.insert(btn, Hidden).unwrap_or_else(|e| {
-                                log::error!("Unable to insert btn into hiddens - {}", e);
-                                None
-                            });
-                        }
-                    }
+                    let hidden = self
+                        .hud_entities
+                        .first()
+                        .map_or(false, |e| world.read_storage::<Hidden>().contains(*e));
+                    self.set_hud_hidden(world, !hidden);
                 }
                 L => t = Trans::Switch(Box::new(LevelSelectState::default())),
                 Escape => {
-                    if let Some(btn) = self.score_button {
-                        world
-                            .write_storage::<Hidden>()
-                            .insert(btn, Hidden)
-                            .unwrap_or_else(|err| {
-                                log::warn!("Error hiding things for pausing: {}", err);
-                                None
-                            });
-                    }
+                    self.set_hud_hidden(world, true);
 
                     t = Trans::Push(Box::new(PausedState::default()));
                 }
-                N => self.set_gameplay_mode(GamePlayingMode::Nudger, world),
-                T => self.set_gameplay_mode(GamePlayingMode::TradeOff, world),
-                C => self.set_gameplay_mode(GamePlayingMode::Crazy, world),
-                K => self.set_gameplay_mode(GamePlayingMode::AllTheColliders, world),
-                F => self.set_gameplay_mode(GamePlayingMode::Frenzy, world),
-                B => self.set_gameplay_mode(GamePlayingMode::Boring, world),
                 Key0 | Key1 | Key2 | Key3 | Key4 | Key5 | Key6 | Key7 | Key8 | Key9 => {
-                    if self.level_path.contains("pg-")
-                        || self.level_path.contains(RT_PROCGEN_FILENAME)
+                    //number keys pick tribes instead when they are moved one at a time
+                    let independent = world.read_resource::<TribeControl>().independent;
+                    if !independent
+                        && (self.level_path.contains("pg-")
+                            || self.level_path.contains(RT_PROCGEN_FILENAME))
                     {
                         self.save_pg_level(key_code);
                     }
                 }
-                _ => self.actions.iter().for_each(|(k, v)| {
-                    if &key_code == k {
-                        t = Trans::Switch(Box::new(PuzzleState::new(v.clone())));
+                _ => {
+                    let mode = world
+                        .read_resource::<GameModeManager>()
+                        .modes
+                        .from_key(key_code);
+                    match mode {
+                        Some(mode) => self.set_gameplay_mode(mode, world),
+                        None => self.actions.iter().for_each(|(k, v)| {
+                            if &key_code == k {
+                                t = Trans::Switch(Box::new(PuzzleState::new(v.clone())));
+                            }
+                        }),
                     }
-                }),
+                }
             },
             StateEvent::Window(Event::WindowEvent { event, .. }) => match event {
                 WindowEvent::CloseRequested | WindowEvent::Destroyed => {
@@ -260,8 +321,23 @@ impl SimpleState for PuzzleState {
             self.ws = game_state.ws;
         }
 
+        let music_context = if self.death_timer.is_some() {
+            MusicContext::LossZoom
+        } else if data.world.read_resource::<GameModeManager>().current_mode
+            == GamePlayingMode::Boring
+        {
+            MusicContext::Puzzle
+        } else {
+            MusicContext::SpecialMode
+        };
+        set_music_context(data.world, music_context);
+
         if let GameStateEnum::End { lost_position } = self.ws {
             let won = lost_position.is_none();
+            if won && data.world.read_resource::<LevelScript>().is_holding() {
+                //wait for the outro from the level script
+                return t;
+            }
 
             if let Either::One(lvl_index) = Level::get_seed_index_from_path(&self.level_path) {
                 if lvl_index >= get_levels_str().len() - 1 && won {
@@ -281,7 +357,8 @@ impl SimpleState for PuzzleState {
 
                 let (so_far, total, ent) = self.death_timer.take().unwrap_or_else(|| {
                     let pos = lost_position.unwrap_or_default();
-                    let (x, y) = UpdateTileTransforms::tile_to_xyz(pos);
+                    let offset = get_grid_offset(&data.world.read_resource::<LTConfig>().conf);
+                    let (x, y) = UpdateTileTransforms::tile_to_xyz(pos, offset);
 
                     let mut trans = Transform::default();
                     trans.set_translation_xyz(x, y, 2.0);
@@ -319,10 +396,6 @@ impl SimpleState for PuzzleState {
             }
         }
 
-        // {
-        //     let client = &data.world.read_resource::<SteamworksHolder>().0;
-        // }
-
         t
     }
 }
@@ -352,7 +425,7 @@ impl PuzzleState {
             seed: Some(current_index),
             specials: 50,
             messages: Vec::new(),
-            is_csv: false,
+            ..Default::default()
         };
         let contents_str = ron::to_string(&contents).unwrap_or_default();
 
@@ -365,19 +438,24 @@ impl PuzzleState {
     fn set_gameplay_mode(&mut self, new_mode: GamePlayingMode, world: &mut World) {
         let can_change = {
             let mut current_mode = world.write_resource::<GameModeManager>();
+            let mut events = world.write_resource::<GameEventChannel>();
             if current_mode.current_mode != new_mode {
-                current_mode.set_mode(new_mode)
+                current_mode.set_mode(new_mode, &mut events)
             } else {
                 false
             }
         };
 
         if can_change {
+            self.reset_fx_entities(world);
             self.make_fx_entities(world);
         } else {
-            world
-                .write_resource::<GameModeManager>()
-                .set_mode(GamePlayingMode::Boring);
+            {
+                let mut events = world.write_resource::<GameEventChannel>();
+                world
+                    .write_resource::<GameModeManager>()
+                    .set_mode(GamePlayingMode::Boring, &mut events);
+            }
             self.reset_fx_entities(world);
         }
     }
@@ -385,34 +463,27 @@ impl PuzzleState {
     fn make_fx_entities(&mut self, world: &mut World) {
         let mut entities_to_make = Vec::new();
         {
+            let fx_layers = {
+                let gm = world.read_resource::<GameModeManager>();
+                gm.modes.get(gm.current_mode).fx_layers()
+            };
             let sprite_renderers = world.read_storage::<SpriteRender>();
             let tiletransforms = world.read_storage::<TileTransform>();
 
             for e in &world.read_resource::<EntityHolder>().tiles {
                 if let Some(spr) = sprite_renderers.get(*e) {
                     if let Some(tt) = tiletransforms.get(*e) {
-                        let (tt1, tt2) = {
-                            // let tw = TILE_WIDTH as i32 / 2;
-                            // let th = TILE_HEIGHT as i32 / 2;
-                            let tw = 1;
-                            let th = 1;
-
-                            let mut tt1 = *tt;
-                            let mut tt2 = *tt;
-
-                            tt1.set_offsets((tw, th));
-                            tt2.set_offsets((-tw, -th));
-
-                            (tt1, tt2)
-                        };
-
-                        let ti1 = Tint(Srgba::new(1.0, 0.0, 0.0, 0.5));
-                        let ti2 = Tint(Srgba::new(0.25, 0.0, 1.0, 0.5));
-
-                        let spr = spr.clone();
-
-                        entities_to_make.push((spr.clone(), tt1, ti1));
-                        entities_to_make.push((spr.clone(), tt2, ti2));
+                        for layer in &fx_layers {
+                            let mut layer_tt = *tt;
+                            layer_tt.set_offsets(layer.offset);
+
+                            let [r, g, b, a] = layer.colour;
+                            entities_to_make.push((
+                                spr.clone(),
+                                layer_tt,
+                                Tint(Srgba::new(r, g, b, a)),
+                            ));
+                        }
                     }
                 }
             }
@@ -438,6 +509,7 @@ impl PuzzleState {
                 .with(tt)
                 .with(nothing_tint)
                 .with(TintOverride(hacker_tint))
+                .with(ModeFx::default())
                 // .with(anim)
                 .with(Animator::<TintAnimatorData>::default())
                 .with(trans)
@@ -447,33 +519,180 @@ impl PuzzleState {
     }
 }
 
-///Adds an entity with UiText to mark the score to the player
-fn add_score(world: &mut World) -> Entity {
-    let (sf_x, sf_y) = get_scaling_factor();
+///Adds every entity in the HUD, and returns them all
+///
+///Each corner has its own anchor, so the HUD stays in place when the resolution changes
+///
+///  - **level_name** is the name of the level, to show at the bottom
+///  - **best_and_par** is the text for the best score and par of the level
+fn add_hud(world: &mut World, level_name: String, best_and_par: String) -> Vec<Entity> {
+    use Anchor::*;
+
+    let mut hud = add_budget_meter(world);
+    hud.push(add_hud_text(
+        world,
+        "hud_moves",
+        TopLeft,
+        [50.0, -50.0, 333.3, 40.0],
+        25.0,
+        HudElement::Moves,
+        String::new(),
+    ));
+    hud.push(add_hud_text(
+        world,
+        "hud_best_and_par",
+        TopLeft,
+        [50.0, -90.0, 333.3, 40.0],
+        20.0,
+        HudElement::Fixed,
+        best_and_par,
+    ));
+    hud.push(add_hud_text(
+        world,
+        "hud_tribes",
+        BottomLeft,
+        [50.0, 50.0, 400.0, 200.0],
+        20.0,
+        HudElement::Tribes,
+        String::new(),
+    ));
+    hud.push(add_hud_text(
+        world,
+        "hud_mode_icon",
+        BottomRight,
+        [-50.0, 50.0, 80.0, 80.0],
+        60.0,
+        HudElement::ModeIcon,
+        String::new(),
+    ));
+    hud.push(add_hud_text(
+        world,
+        "hud_specials",
+        BottomRight,
+        [-50.0, 140.0, 450.0, 300.0],
+        20.0,
+        HudElement::Specials,
+        String::new(),
+    ));
+    hud.push(add_hud_text(
+        world,
+        "hud_level_name",
+        BottomMiddle,
+        [0.0, 30.0, 800.0, 40.0],
+        25.0,
+        HudElement::Fixed,
+        level_name,
+    ));
+    hud
+}
+
+///Adds an entity with UiText for one part of the HUD
+///
+///  - **anchor** is used for the anchor, the pivot and the text alignment, so the text sits in that corner
+///  - **layout** is the unscaled [x, y, width, height]
+///  - **font_size** is the unscaled font size
+///  - **element** is which part of the HUD the text is
+///  - **text** is the text to start with - parts which aren't Fixed get theirs from the HudSystem
+fn add_hud_text(
+    world: &mut World,
+    id: &str,
+    anchor: Anchor,
+    [x, y, width, height]: [f32; 4],
+    font_size: f32,
+    element: HudElement,
+    text: String,
+) -> Entity {
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let trans = UiTransform::new(
-        "score_txt".to_string(),
-        Anchor::TopLeft,
-        Anchor::TopLeft,
-        50.0 * sf_x,
-        -50.0 * sf_y,
+        id.to_string(),
+        anchor,
+        anchor,
+        x * sf_x,
+        y * sf_y,
         0.5,
-        333.3 * sf_x,
-        500.0 * sf_y,
+        width * sf_x,
+        height * sf_y,
     );
     let txt = UiText::new(
         load_font(world, "ZxSpectrumBold"),
-        "Current Score: 0".to_string(),
-        [1.0, 1.0, 1.0, 0.5],
-        sf_x * 25.0,
+        text,
+        [1.0, 1.0, 1.0, 0.8],
+        sf_y * font_size,
         LineMode::Wrap,
+        anchor,
+    );
+    world
+        .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
+        .with(trans)
+        .with(txt)
+        .with(HudText::new(element))
+        .build()
+}
+
+///Adds a bar to show the player how many special moves are left, along with a dim track behind it
+fn add_budget_meter(world: &mut World) -> Vec<Entity> {
+    let (sf_x, sf_y) = get_scaling_factor(world);
+    let get_trans = |id: &str, z: f32| {
+        UiTransform::new(
+            id.to_string(),
+            Anchor::TopLeft,
+            Anchor::MiddleLeft,
+            50.0 * sf_x,
+            -30.0 * sf_y,
+            z,
+            333.3 * sf_x,
+            12.0 * sf_y,
+        )
+    };
+
+    let track_trans = get_trans("budget_meter_track", 0.5);
+    let track = world
+        .create_entity()
+        .with(ScreenScaled::new(&track_trans, None, (sf_x, sf_y)))
+        .with(track_trans)
+        .with(UiImage::SolidColor([1.0, 1.0, 1.0, 0.15]))
+        .build();
+
+    let bar_trans = get_trans("budget_meter_bar", 0.6);
+    let bar = world
+        .create_entity()
+        .with(ScreenScaled::new(&bar_trans, None, (sf_x, sf_y)))
+        .with(bar_trans)
+        .with(UiImage::SolidColor([0.6, 0.2, 1.0, 0.8]))
+        .with(BudgetMeter::default())
+        .build();
+
+    vec![track, bar]
+}
+
+///Adds an entity with UiText to show the boss health to the player
+fn add_boss_hp(world: &mut World) -> Entity {
+    let (sf_x, sf_y) = get_scaling_factor(world);
+    let trans = UiTransform::new(
+        "boss_hp_txt".to_string(),
+        Anchor::TopMiddle,
+        Anchor::TopMiddle,
+        0.0,
+        -25.0 * sf_y,
+        0.5,
+        1000.0 * sf_x,
+        50.0 * sf_y,
+    );
+    let txt = UiText::new(
+        load_font(world, "ZxSpectrumBold"),
+        String::new(),
+        [1.0, 0.3, 0.3, 1.0],
+        sf_x * 25.0,
+        LineMode::Single,
         Anchor::Middle,
     );
     world
         .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
         .with(trans)
         .with(txt)
-        .with(Interactable)
-        .with(Score)
+        .with(BossHpText)
         .build()
 }
 
@@ -490,6 +709,7 @@ fn get_no_of_moves(world: &World) -> i32 {
 ///  - **path** is the Path to the level eg. *"lvl-01.png"*
 fn load_level(world: &mut World, sprites_handle: Handle<SpriteSheet>, lvl: Room) -> EntityHolder {
     let mut holder = EntityHolder::new();
+    world.insert(OccupancyGrid::default());
 
     if lvl.is_empty() {
         return holder;
@@ -510,7 +730,6 @@ fn load_level(world: &mut World, sprites_handle: Handle<SpriteSheet>, lvl: Room)
             let tt = TileTransform::new(x as i32, y as i32);
             let tint = Tint(Srgba::new(1.0, 1.0, 1.0, 1.0));
 
-            world.insert(ColliderList::new());
             world.insert(GameState::default());
 
             let mut trans = Transform::default();
@@ -542,34 +761,109 @@ fn load_level(world: &mut World, sprites_handle: Handle<SpriteSheet>, lvl: Room)
                             .with(lonely_tribes_components::player::Player::new(id))
                             .with(Animator::<MovementAnimationData>::default())
                             .with(Animator::<RotationAnimationData>::default())
+                            .with(Animator::<ScaleAnimationData>::default())
                             .with(Animator::<TintAnimatorData>::default())
-                            .with(PointLight::new(3))
+                            .with(PointLight::for_tribe(3, id))
                             .with(tint)
                             .build();
                         holder.add_player_entity(ent);
                     }
                     Tag::Collision => {
+                        let mut builder = world
+                            .create_entity()
+                            .with(spr)
+                            .with(tt)
+                            .with(trans)
+                            .with(Collider::default())
+                            .with(Animator::<TintAnimatorData>::default())
+                            .with(tint);
+                        if let Some(light) = PointLight::from_spr(tag) {
+                            builder = builder.with(light);
+                        }
+                        let ent = builder.build();
+                        holder.add_tile(ent);
+                    }
+                    Tag::Pushable => {
+                        trans.set_translation_z(0.15);
+
                         let ent = world
                             .create_entity()
                             .with(spr)
                             .with(tt)
                             .with(trans)
                             .with(Collider::default())
+                            .with(Pushable)
+                            .with(Animator::<MovementAnimationData>::default())
                             .with(Animator::<TintAnimatorData>::default())
                             .with(tint)
                             .build();
                         holder.add_tile(ent);
                     }
+                    Tag::Floor => match Floor::from_spr(tag) {
+                        Some(Floor(effect)) => {
+                            let effect = match effect {
+                                FloorEffect::Teleporter(_) => FloorEffect::Teleporter(
+                                    lvl.links.get(&(x, y)).copied().unwrap_or_default(),
+                                ),
+                                _ => effect,
+                            };
+
+                            let ent = world
+                                .create_entity()
+                                .with(spr)
+                                .with(tt)
+                                .with(trans)
+                                .with(Floor(effect))
+                                .with(Animator::<TintAnimatorData>::default())
+                                .with(tint)
+                                .build();
+                            holder.add_tile(ent);
+                        }
+                        None => other(),
+                    },
+                    Tag::Hazard => match Hazard::from_spr(tag) {
+                        Some(hazard) => {
+                            let mut hazard = Hazard {
+                                timer: lvl.hazard_timers.get(&(x, y)).copied(),
+                                ..hazard
+                            };
+                            hazard.update(0);
+
+                            let ent = world
+                                .create_entity()
+                                .with(SpriteRender::new(sprites_handle.clone(), hazard.sprite()))
+                                .with(tt)
+                                .with(trans)
+                                .with(hazard)
+                                .with(Animator::<TintAnimatorData>::default())
+                                .with(tint)
+                                .build();
+                            holder.add_tile(ent);
+                        }
+                        None => other(),
+                    },
                     Tag::Trigger(trigger_type) => {
-                        let ent = world
+                        let link = lvl.links.get(&(x, y)).copied().unwrap_or_default();
+                        let trigger_type = trigger_type.with_link(link);
+
+                        let mut builder = world
                             .create_entity()
                             .with(spr)
                             .with(tt)
                             .with(trans)
-                            .with(Collider::new(trigger_type))
                             .with(Animator::<TintAnimatorData>::default())
-                            .with(tint)
-                            .build();
+                            .with(tint);
+                        builder = match Linked::from_spr(tag) {
+                            Some(linked) => {
+                                let linked = Linked {
+                                    trigger: trigger_type,
+                                    ..linked
+                                };
+                                builder.with(linked.collider()).with(linked)
+                            }
+                            None => builder.with(Collider::new(trigger_type)),
+                        };
+                        let ent = builder.build();
                         holder.add_tile(ent);
                     }
                     _ => other(),
@@ -581,3 +875,98 @@ fn load_level(world: &mut World, sprites_handle: Handle<SpriteSheet>, lvl: Room)
 
     holder
 }
+
+///Adds the NPCs for a level into the world
+///
+///  - **world** is the World to add them to
+///  - **sprites_handle** is the handle to the spritesheet
+///  - **npcs** is each NPC, as (x, y, movement pattern)
+///  - **holder** is the EntityHolder to add them to
+fn load_npcs(
+    world: &mut World,
+    sprites_handle: Handle<SpriteSheet>,
+    npcs: &[(usize, usize, NpcPattern)],
+    holder: &mut EntityHolder,
+) {
+    for (x, y, pattern) in npcs {
+        let mut trans = Transform::default();
+        trans.set_translation_z(0.2);
+
+        let ent = world
+            .create_entity()
+            .with(SpriteRender::new(
+                sprites_handle.clone(),
+                SpriteRequest::Npc as i32 as usize,
+            ))
+            .with(TileTransform::from((*x, *y)))
+            .with(trans)
+            .with(Npc::new(pattern.clone()))
+            .with(Animator::<MovementAnimationData>::default())
+            .with(Animator::<TintAnimatorData>::default())
+            .with(Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)))
+            .build();
+        holder.add_tile(ent);
+    }
+}
+
+///Adds the boss and its respawn points into the world
+///
+///  - **world** is the World to add them to
+///  - **sprites_handle** is the handle to the spritesheet
+///  - **config** is the boss section of the level
+///  - **holder** is the EntityHolder to add them to
+fn load_boss(
+    world: &mut World,
+    sprites_handle: Handle<SpriteSheet>,
+    config: &BossConfig,
+    holder: &mut EntityHolder,
+) {
+    let tint = Tint(Srgba::new(1.0, 1.0, 1.0, 1.0));
+    let mut trans = Transform::default();
+    trans.set_translation_z(0.2);
+
+    let ent = world
+        .create_entity()
+        .with(SpriteRender::new(
+            sprites_handle.clone(),
+            SpriteRequest::Boss as i32 as usize,
+        ))
+        .with(TileTransform::from((config.x, config.y)))
+        .with(trans.clone())
+        .with(Boss::new(config))
+        .with(Animator::<TintAnimatorData>::default())
+        .with(tint)
+        .build();
+    holder.add_tile(ent);
+
+    trans.set_translation_z(0.05);
+    for (x, y, tribe, every) in &config.respawns {
+        let member_sprite = match tribe {
+            0 => SpriteRequest::Player0,
+            1 => SpriteRequest::Player1,
+            2 => SpriteRequest::Player2,
+            _ => SpriteRequest::Player3,
+        };
+
+        let ent = world
+            .create_entity()
+            .with(SpriteRender::new(
+                sprites_handle.clone(),
+                SpriteRequest::RespawnPoint as i32 as usize,
+            ))
+            .with(TileTransform::from((*x, *y)))
+            .with(trans.clone())
+            .with(RespawnPoint {
+                tribe: *tribe,
+                every: *every,
+                member_sprite: SpriteRender::new(
+                    sprites_handle.clone(),
+                    member_sprite as i32 as usize,
+                ),
+            })
+            .with(Animator::<TintAnimatorData>::default())
+            .with(tint)
+            .build();
+        holder.add_tile(ent);
+    }
+}
@@ -5,7 +5,11 @@ use amethyst::{
     ui::{Anchor, LineMode, UiText, UiTransform},
     GameData, SimpleState, SimpleTrans, StateData, StateEvent,
 };
-use lonely_tribes_lib::states_util::{get_scaling_factor, load_font};
+use lonely_tribes_components::screen_scaled::ScreenScaled;
+use lonely_tribes_lib::{
+    audio::{set_music_context, MusicContext},
+    states_util::{get_scaling_factor, load_font},
+};
 
 ///Text displayed in HelpState
 pub const HELP_TXT: &str = include_str!("help_text.txt");
@@ -19,6 +23,7 @@ impl SimpleState for HelpState {
         let world = data.world;
         world.delete_all();
 
+        set_music_context(world, MusicContext::Menu);
         get_help_txt(world);
     }
 
@@ -43,7 +48,7 @@ impl SimpleState for HelpState {
 ///
 ///By default, it uses Atkinson Hyperlegible
 fn get_help_txt(world: &mut World) {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let trans = UiTransform::new(
         "help_txt".to_string(),
         Anchor::Middle,
@@ -62,5 +67,10 @@ fn get_help_txt(world: &mut World) {
         LineMode::Wrap,
         Anchor::MiddleLeft,
     );
-    world.create_entity().with(trans).with(txt).build();
+    world
+        .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
+        .with(trans)
+        .with(txt)
+        .build();
 }
@@ -6,8 +6,11 @@ use amethyst::{
     ui::{Anchor, Interactable, LineMode, UiEventType, UiImage, UiText, UiTransform},
     GameData, SimpleState, SimpleTrans, StateData, StateEvent, Trans,
 };
+use lonely_tribes_components::screen_scaled::ScreenScaled;
 use lonely_tribes_generation::level::RT_PROCGEN_FILENAME;
 use lonely_tribes_lib::{
+    audio::{set_music_context, MusicContext},
+    config::LTConfig,
     high_scores::HighScores,
     states_util::{
         get_levels, get_scaling_factor, levels_len, load_font, load_sprite_sheet, LevelType,
@@ -40,6 +43,7 @@ impl SimpleState for LevelSelectState {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;
         world.delete_all();
+        set_music_context(world, MusicContext::Menu);
 
         let (buttons, next_level, proc_gen, lr) = create_lvl_select_btns(world, 0);
         self.buttons = buttons;
@@ -165,7 +169,7 @@ fn create_lvl_select_btns(
     world: &mut World,
     current_screen: usize,
 ) -> (HashMap<Entity, String>, usize, Entity, (Entity, Entity)) {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     world.delete_all();
 
     let mut map: HashMap<Entity, String> = HashMap::new();
@@ -204,11 +208,16 @@ fn create_lvl_select_btns(
     );
     world
         .create_entity()
+        .with(ScreenScaled::new(
+            &main_trans,
+            Some(main_txt.font_size),
+            (sf_x, sf_y),
+        ))
         .with(main_trans)
         .with(main_txt)
         .build();
 
-    let next_level = high_scores.find_next_level();
+    let next_level = high_scores.find_next_level(world.read_resource::<LTConfig>().flags.debug);
     for (i, (level, level_type)) in get_levels()
         .iter()
         .skip(current_screen * MAX_LEVELS_ONE_SCREEN as usize)
@@ -270,7 +279,8 @@ fn create_lvl_select_btns(
             Anchor::MiddleLeft,
         );
 
-        let mut entity = world.create_entity().with(trans).with(txt);
+        let scaled = ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y));
+        let mut entity = world.create_entity().with(scaled).with(trans).with(txt);
         if can_be_played {
             entity = entity.with(Interactable);
         }
@@ -300,6 +310,7 @@ fn create_lvl_select_btns(
         );
         world
             .create_entity()
+            .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
             .with(trans)
             .with(txt)
             .with(Interactable)
@@ -326,6 +337,7 @@ fn create_lvl_select_btns(
                 world
                     .create_entity()
                     .with(right)
+                    .with(ScreenScaled::new_square(&right_trans, (sf_x, sf_y)))
                     .with(right_trans)
                     .with(Interactable)
                     .build()
@@ -349,6 +361,7 @@ fn create_lvl_select_btns(
                 world
                     .create_entity()
                     .with(left)
+                    .with(ScreenScaled::new_square(&left_trans, (sf_x, sf_y)))
                     .with(left_trans)
                     .with(Interactable)
                     .build()
@@ -1,3 +1,4 @@
+pub mod achievements_state;
 pub mod afterwards_state;
 pub mod game_state;
 pub mod help_state;
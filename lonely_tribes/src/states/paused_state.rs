@@ -8,7 +8,7 @@ use amethyst::{
     ui::{Anchor, Interactable, LineMode, UiEvent, UiEventType, UiText, UiTransform},
     GameData, SimpleState, SimpleTrans, StateData, StateEvent,
 };
-use lonely_tribes_components::data_holder::EntityHolder;
+use lonely_tribes_components::{data_holder::EntityHolder, screen_scaled::ScreenScaled};
 use lonely_tribes_lib::{
     states_util::{get_scaling_factor, load_font},
     HOVER_COLOUR,
@@ -166,7 +166,7 @@ pub fn show_entities(world: &mut World, entities: Vec<Entity>) {
 ///
 /// Returns a HashMap of all the buttons, and the title
 pub fn get_pause_buttons(world: &mut World) -> (HashMap<Entity, PausedStateMenuAction>, Entity) {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let mut map = HashMap::new();
 
     let bold_font_handle = load_font(world, "ZxSpectrumBold");
@@ -192,6 +192,11 @@ pub fn get_pause_buttons(world: &mut World) -> (HashMap<Entity, PausedStateMenuA
     );
     let top = world
         .create_entity()
+        .with(ScreenScaled::new(
+            &welcome_trans,
+            Some(welcome_txt.font_size),
+            (sf_x, sf_y),
+        ))
         .with(welcome_trans)
         .with(welcome_txt)
         .build();
@@ -224,6 +229,11 @@ pub fn get_pause_buttons(world: &mut World) -> (HashMap<Entity, PausedStateMenuA
         );
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &toggle_btn_trans,
+                Some(toggle_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
             .with(toggle_btn_trans)
             .with(toggle_btn_txt)
             .with(Interactable)
@@ -251,6 +261,11 @@ pub fn get_pause_buttons(world: &mut World) -> (HashMap<Entity, PausedStateMenuA
         );
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &quit_btn_trans,
+                Some(quit_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
             .with(quit_btn_trans)
             .with(quit_btn_txt)
             .with(Interactable)
@@ -278,6 +293,11 @@ pub fn get_pause_buttons(world: &mut World) -> (HashMap<Entity, PausedStateMenuA
         );
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &quit_btn_trans,
+                Some(quit_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
             .with(quit_btn_trans)
             .with(quit_btn_txt)
             .with(Interactable)
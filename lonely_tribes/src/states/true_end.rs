@@ -5,7 +5,11 @@ use amethyst::{
     ui::{Anchor, Interactable, LineMode, UiEventType, UiText, UiTransform},
     GameData, SimpleState, SimpleTrans, StateData, StateEvent, Trans,
 };
-use lonely_tribes_lib::states_util::{get_scaling_factor, load_font};
+use lonely_tribes_components::screen_scaled::ScreenScaled;
+use lonely_tribes_lib::{
+    audio::{set_music_context, MusicContext},
+    states_util::{get_scaling_factor, load_font},
+};
 
 ///State for when the user has finished all levels
 #[derive(Default)]
@@ -18,6 +22,7 @@ impl SimpleState for TrueEnd {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         let world = data.world;
 
+        set_music_context(world, MusicContext::TrueEnd);
         self.btn = Some(get_true_end_txt(world));
     }
 
@@ -73,7 +78,7 @@ impl SimpleState for TrueEnd {
 ///
 ///Returns the entity of that text, for checking when it was clicked
 pub fn get_true_end_txt(world: &mut World) -> Entity {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let trans = UiTransform::new(
         "end_txt".to_string(),
         Anchor::Middle,
@@ -94,6 +99,7 @@ pub fn get_true_end_txt(world: &mut World) -> Entity {
     );
     world
         .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
         .with(trans)
         .with(txt)
         .with(Interactable)
@@ -1,4 +1,6 @@
-use super::{help_state::HelpState, level_select::LevelSelectState};
+use super::{
+    achievements_state::AchievementsState, help_state::HelpState, level_select::LevelSelectState,
+};
 use amethyst::{
     core::{
         ecs::{Builder, Entity, World, WorldExt},
@@ -8,12 +10,12 @@ use amethyst::{
     winit::Window,
     GameData, SimpleState, SimpleTrans, StateData, StateEvent,
 };
-use lonely_tribes_components::text_wobble::TextWobble;
+use lonely_tribes_components::{screen_scaled::ScreenScaled, text_wobble::TextWobble};
 use lonely_tribes_lib::{
-    audio::init_audio,
-    config::{change_screen, DEFAULT_DPI, DEFAULT_SCREEN_RES},
+    audio::{init_audio, set_music_context, MusicContext},
+    config::{change_screen, LTConfig, DEFAULT_DPI, DEFAULT_SCREEN_RES},
     states_util::{get_scaling_factor, load_font},
-    CONFIG, HOVER_COLOUR,
+    HOVER_COLOUR,
 };
 use lonely_tribes_systems::message_system::{MessageList, TimedMessagesToAdd};
 use rand::{thread_rng, Rng};
@@ -40,6 +42,7 @@ pub struct StartGameState {
 pub enum ButtonType {
     Start,
     Help,
+    Achievements,
     Quit,
 }
 
@@ -54,20 +57,23 @@ impl SimpleState for StartGameState {
         world.insert(TimedMessagesToAdd::default());
 
         init_audio(world);
+        set_music_context(world, MusicContext::Menu);
 
-        if CONFIG.conf.screen_dimensions == DEFAULT_SCREEN_RES
-            && CONFIG.conf.dpi_factor == DEFAULT_DPI
-        {
-            log::info!("Def res: changing + restarting.");
-            let w = world.read_resource::<Window>();
-            let monitor = w.get_current_monitor();
-            let res = monitor.get_dimensions();
-            change_screen(
-                res.width as u32,
-                res.height as u32,
-                monitor.get_hidpi_factor(),
-            );
-            std::process::exit(0);
+        let conf = world.read_resource::<LTConfig>().conf;
+        if conf.screen_dimensions == DEFAULT_SCREEN_RES && conf.dpi_factor == DEFAULT_DPI {
+            log::info!("Def res: changing + reloading.");
+            {
+                let w = world.read_resource::<Window>();
+                let monitor = w.get_current_monitor();
+                let res = monitor.get_dimensions();
+                change_screen(
+                    res.width as u32,
+                    res.height as u32,
+                    monitor.get_hidpi_factor(),
+                );
+                w.set_inner_size(res.to_logical(monitor.get_hidpi_factor()));
+            }
+            world.write_resource::<LTConfig>().reload();
         }
 
         self.btns = init_menu(world);
@@ -112,6 +118,11 @@ impl SimpleState for StartGameState {
                                     ButtonType::Help => {
                                         t = SimpleTrans::Switch(Box::new(HelpState::default()));
                                     }
+                                    ButtonType::Achievements => {
+                                        t = SimpleTrans::Switch(Box::new(
+                                            AchievementsState::default(),
+                                        ));
+                                    }
                                     ButtonType::Quit => {
                                         t = SimpleTrans::Quit;
                                     }
@@ -156,7 +167,7 @@ impl SimpleState for StartGameState {
 }
 
 fn init_welcome_msgs(world: &mut World) -> Entity {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let font_handle = load_font(world, "ZxSpectrum");
     let msg = WELCOME_MSGS[thread_rng().gen_range(0..WELCOME_MSGS.len())];
 
@@ -179,14 +190,19 @@ fn init_welcome_msgs(world: &mut World) -> Entity {
         Anchor::Middle,
     );
 
-    world.create_entity().with(trans).with(txt).build()
+    world
+        .create_entity()
+        .with(ScreenScaled::new(&trans, Some(txt.font_size), (sf_x, sf_y)))
+        .with(trans)
+        .with(txt)
+        .build()
 }
 
 ///Function to initialise Start Screen Main Menu
 ///
 /// Returns a hashmap of entities
 fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
-    let (sf_x, sf_y) = get_scaling_factor();
+    let (sf_x, sf_y) = get_scaling_factor(world);
     let bold_font_handle = load_font(world, "ZxSpectrumBold");
     let font_handle = load_font(world, "ZxSpectrum");
     let mut map = HashMap::new();
@@ -212,6 +228,11 @@ fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
     );
     world
         .create_entity()
+        .with(ScreenScaled::new(
+            &welcome_trans,
+            Some(welcome_txt.font_size),
+            (sf_x, sf_y),
+        ))
         .with(welcome_trans)
         .with(welcome_txt)
         .build();
@@ -240,6 +261,11 @@ fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
         ButtonType::Start,
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &start_btn_trans,
+                Some(start_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
             .with(start_btn_trans)
             .with(start_btn_txt)
             .with(TextWobble::new(sf_y * 10.0, sf_y * -85.0, 2.5))
@@ -271,6 +297,11 @@ fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
         ButtonType::Help,
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &help_btn_trans,
+                Some(help_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
             .with(help_btn_trans)
             .with(help_btn_txt)
             .with(TextWobble::new(sf_y * 10.0, sf_y * -145.0, 2.5))
@@ -279,6 +310,42 @@ fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
     );
     //endregion
 
+    //region achievements
+    let achievements_btn_trans = UiTransform::new(
+        String::from("achievements_btn"),
+        Anchor::Middle,
+        Anchor::Middle,
+        0.0,
+        sf_y * -205.0,
+        0.0,
+        sf_x * 1400.0,
+        sf_y * 40.0,
+    );
+    let achievements_btn_txt = UiText::new(
+        font_handle.clone(),
+        String::from("Click here to see Achievements."),
+        [1.0; 4],
+        sf_y * 50.0,
+        LineMode::Single,
+        Anchor::Middle,
+    );
+    map.insert(
+        ButtonType::Achievements,
+        world
+            .create_entity()
+            .with(ScreenScaled::new(
+                &achievements_btn_trans,
+                Some(achievements_btn_txt.font_size),
+                (sf_x, sf_y),
+            ))
+            .with(achievements_btn_trans)
+            .with(achievements_btn_txt)
+            .with(TextWobble::new(sf_y * 10.0, sf_y * -205.0, 2.5))
+            .with(Interactable)
+            .build(),
+    );
+    //endregion
+
     //region quit
     let quit_btn_trans = UiTransform::new(
         String::from("quit_btn"),
@@ -302,6 +369,11 @@ fn init_menu(world: &mut World) -> HashMap<ButtonType, Entity> {
         ButtonType::Quit,
         world
             .create_entity()
+            .with(ScreenScaled::new(
+                &quit_btn_trans,
+                Some(quit_btn_text.font_size),
+                (sf_x, sf_y),
+            ))
             .with(quit_btn_trans)
             .with(quit_btn_text)
             .with(TextWobble::new(sf_y * 10.0, sf_y * -265.0, 2.5))
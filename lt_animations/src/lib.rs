@@ -5,4 +5,5 @@ pub mod data;
 pub mod interpolation;
 pub mod movement;
 pub mod rotation;
+pub mod scale;
 pub mod tint;
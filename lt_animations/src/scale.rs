@@ -0,0 +1,60 @@
+use crate::{
+    data::AnimationData,
+    interpolation::{get_offset_multiplier, AnimInterpolation},
+};
+
+///Data for an animator to grow an entity from nothing back to full size, eg. after teleporting
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScaleAnimationData {
+    ///total time for the animation
+    pub total_time: f32,
+    ///time elapsed so far in the animation
+    pub time_elapsed: f32,
+
+    ///interpolation used by the animation
+    pub interpolation: AnimInterpolation,
+}
+impl AnimationData for ScaleAnimationData {
+    type AnimDataType = f32;
+
+    fn is_done(&self) -> bool {
+        self.time_elapsed >= self.total_time
+    }
+
+    fn add_time(&mut self, time_since_last: f32) {
+        self.time_elapsed += time_since_last;
+    }
+
+    fn get_current(&self) -> Self::AnimDataType {
+        get_offset_multiplier(self.time_elapsed, self.total_time, self.interpolation).min(1.0)
+    }
+}
+
+impl ScaleAnimationData {
+    ///constructor
+    pub fn new(total_time: f32, interpolation: AnimInterpolation) -> Self {
+        Self {
+            total_time,
+            time_elapsed: 0.0,
+            interpolation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn grows_test() {
+        let mut anim = ScaleAnimationData::new(1.0, AnimInterpolation::Linear);
+        assert_eq!(anim.get_current(), 0.0);
+
+        anim.add_time(0.5);
+        assert_eq!(anim.get_current(), 0.5);
+
+        anim.add_time(1.0);
+        assert!(anim.is_done());
+        assert_eq!(anim.get_current(), 1.0);
+    }
+}
@@ -13,6 +13,10 @@ pub struct TintAnimatorData {
     pub end: f32,
     ///Override tint for if the object has an active tint
     pub override_tint: Option<Tint>,
+    ///Start colour of the light on the object, as RGB
+    pub start_colour: [f32; 3],
+    ///End colour of the light on the object, as RGB
+    pub end_colour: [f32; 3],
 
     ///Total animation length
     pub total_time: f32,
@@ -35,11 +39,23 @@ impl TintAnimatorData {
             start,
             end,
             override_tint,
+            start_colour: [1.0; 3],
+            end_colour: [1.0; 3],
             total_time,
             time_elapsed: 0.0,
             interpolation,
         }
     }
+
+    ///Sets the colours of the light to animate between
+    ///
+    ///  - **start** is the colour at the start
+    ///  - **end** is the colour at the end
+    pub fn with_colours(mut self, start: [f32; 3], end: [f32; 3]) -> Self {
+        self.start_colour = start;
+        self.end_colour = end;
+        self
+    }
 }
 
 impl AnimationData for TintAnimatorData {
@@ -54,10 +70,10 @@ impl AnimationData for TintAnimatorData {
     }
 
     fn get_current(&self) -> Self::AnimDataType {
+        let multiplier =
+            get_offset_multiplier(self.time_elapsed, self.total_time, self.interpolation);
         let factor = {
-            let f = self.start
-                + (self.end - self.start)
-                    * get_offset_multiplier(self.time_elapsed, self.total_time, self.interpolation);
+            let f = self.start + (self.end - self.start) * multiplier;
             let str_version = format!("{:03}", f);
             str_version.parse().unwrap_or_else(|err| {
                 log::warn!("Couldn't parse into str because: {}", err);
@@ -68,7 +84,12 @@ impl AnimationData for TintAnimatorData {
         if let Some(or) = self.override_tint {
             Tint(Srgba::new(or.0.red, or.0.green, or.0.blue, factor))
         } else {
-            Tint(Srgba::new(factor, factor, factor, factor))
+            let colour = |i: usize| {
+                let c =
+                    self.start_colour[i] + (self.end_colour[i] - self.start_colour[i]) * multiplier;
+                c * factor
+            };
+            Tint(Srgba::new(colour(0), colour(1), colour(2), factor))
         }
     }
 }
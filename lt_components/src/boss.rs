@@ -0,0 +1,216 @@
+use amethyst::{
+    core::ecs::{Component, DenseVecStorage, NullStorage},
+    renderer::SpriteRender,
+};
+use serde::{Deserialize, Serialize};
+
+///The boss section of a level file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BossConfig {
+    ///The X position of the boss
+    pub x: usize,
+    ///The Y position of the boss
+    pub y: usize,
+    ///How much health the boss starts with
+    pub hp: u32,
+    ///How many turns between each boss attack - 0 means the boss never attacks
+    #[serde(default)]
+    pub attack_every: u32,
+    ///How many tribe members each boss attack gets rid of
+    #[serde(default)]
+    pub attack_kills: u32,
+    ///Where tribe members respawn, as (x, y, tribe id, turns between each respawn)
+    #[serde(default)]
+    pub respawns: Vec<(usize, usize, usize, u32)>,
+}
+
+///Gets how much damage a tribe member does when it hits the boss - each merged member doubles it
+///
+///  - **no_players** is how many other members have been merged into this one
+pub fn impact_damage(no_players: u16) -> u32 {
+    2_u32.saturating_pow(no_players as u32)
+}
+
+///Checks whether or not something which happens every few turns is due
+///
+///  - **every** is how many turns between each time - if 0, it is never due
+///  - **turn** is how many turns have happened in the level
+fn is_due(every: u32, turn: u32) -> bool {
+    every != 0 && turn != 0 && turn % every == 0
+}
+
+///Component for a boss, which tribe members walk into to damage
+#[derive(Clone, Debug)]
+pub struct Boss {
+    ///How much health the boss started with
+    pub max_hp: u32,
+    ///How much health the boss has left
+    pub hp: u32,
+    ///How many turns between each attack - 0 means the boss never attacks
+    pub attack_every: u32,
+    ///How many tribe members each attack gets rid of
+    pub attack_kills: u32,
+    ///How many turns have happened in the fight
+    pub turn: u32,
+}
+impl Boss {
+    ///Constructor for a Boss at full health, from the level's boss section
+    pub fn new(config: &BossConfig) -> Self {
+        Self {
+            max_hp: config.hp,
+            hp: config.hp,
+            attack_every: config.attack_every,
+            attack_kills: config.attack_kills,
+            turn: 0,
+        }
+    }
+
+    ///Damages the boss with a tribe member, and returns how much damage was done
+    ///
+    ///  - **no_players** is how many other members have been merged into the one hitting the boss
+    pub fn hit(&mut self, no_players: u16) -> u32 {
+        let damage = impact_damage(no_players).min(self.hp);
+        self.hp -= damage;
+        damage
+    }
+
+    ///Whether or not the boss has run out of health
+    pub fn is_defeated(&self) -> bool {
+        self.hp == 0
+    }
+
+    ///Whether or not the boss attacks on the current turn
+    pub fn attack_due(&self) -> bool {
+        is_due(self.attack_every, self.turn)
+    }
+
+    ///Picks which tribe members get attacked on the current turn
+    ///
+    ///The picks look random, but are decided by the turn so that a fight always plays out the same way
+    ///
+    ///  - **no_members** is how many tribe members there are to pick from
+    pub fn attack_targets(&self, no_members: usize) -> Vec<usize> {
+        let mut left: Vec<usize> = (0..no_members).collect();
+        let mut state = self.turn.wrapping_mul(2_654_435_761).max(1);
+        let mut targets = Vec::new();
+
+        for _ in 0..self.attack_kills {
+            if left.is_empty() {
+                break;
+            }
+
+            //xorshift
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            targets.push(left.swap_remove(state as usize % left.len()));
+        }
+
+        targets
+    }
+
+    ///Gets the text for the boss health bar
+    ///
+    ///  - **width** is how many characters wide the bar is
+    pub fn hp_bar(&self, width: usize) -> String {
+        let filled = if self.max_hp == 0 {
+            0
+        } else {
+            (self.hp as usize * width + self.max_hp as usize - 1) / self.max_hp as usize
+        };
+
+        format!(
+            "BOSS [{}{}] {}/{}",
+            "#".repeat(filled),
+            "-".repeat(width - filled),
+            self.hp,
+            self.max_hp
+        )
+    }
+}
+impl Component for Boss {
+    type Storage = DenseVecStorage<Self>;
+}
+
+///Component for a tile where new members of a tribe appear during a boss fight
+#[derive(Clone, Debug)]
+pub struct RespawnPoint {
+    ///The id of the tribe
+    pub tribe: usize,
+    ///How many turns between each respawn - 0 means it never respawns anyone
+    pub every: u32,
+    ///The sprite for the members that respawn
+    pub member_sprite: SpriteRender,
+}
+impl RespawnPoint {
+    ///Whether or not a member respawns on a turn
+    ///
+    ///  - **turn** is how many turns have happened in the fight
+    pub fn is_due(&self, turn: u32) -> bool {
+        is_due(self.every, turn)
+    }
+}
+impl Component for RespawnPoint {
+    type Storage = DenseVecStorage<Self>;
+}
+
+///Marker component to mark UiText objects to show the boss health
+#[derive(Default)]
+pub struct BossHpText;
+
+impl Component for BossHpText {
+    type Storage = NullStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boss(hp: u32, attack_every: u32, attack_kills: u32) -> Boss {
+        Boss::new(&BossConfig {
+            x: 0,
+            y: 0,
+            hp,
+            attack_every,
+            attack_kills,
+            respawns: vec![],
+        })
+    }
+
+    #[test]
+    pub fn damage_test() {
+        assert_eq!(impact_damage(0), 1);
+        assert_eq!(impact_damage(1), 2);
+        assert_eq!(impact_damage(3), 8);
+        assert_eq!(impact_damage(40), u32::MAX);
+
+        let mut b = boss(10, 0, 0);
+        assert_eq!(b.hit(2), 4);
+        assert_eq!(b.hp_bar(5), "BOSS [###--] 6/10");
+        assert_eq!(b.hit(5), 6, "Can't do more damage than the boss has health");
+        assert!(b.is_defeated());
+        assert_eq!(b.hp_bar(5), "BOSS [-----] 0/10");
+    }
+
+    #[test]
+    pub fn attack_test() {
+        let mut b = boss(10, 3, 2);
+        let due: Vec<bool> = (0..7)
+            .map(|turn| {
+                b.turn = turn;
+                b.attack_due()
+            })
+            .collect();
+        assert_eq!(due, vec![false, false, false, true, false, false, true]);
+
+        let targets = b.attack_targets(5);
+        assert_eq!(targets.len(), 2);
+        assert_ne!(targets[0], targets[1]);
+        assert!(targets.iter().all(|t| *t < 5));
+        assert_eq!(targets, b.attack_targets(5), "Attacks are deterministic");
+
+        assert_eq!(b.attack_targets(1).len(), 1);
+        assert!(b.attack_targets(0).is_empty());
+        assert!(!boss(10, 0, 2).attack_due());
+    }
+}
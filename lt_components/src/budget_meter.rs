@@ -0,0 +1,85 @@
+use amethyst::core::ecs::{Component, DenseVecStorage};
+
+///How quickly the budget meter catches up with the moves left, as a fraction of the gap per second
+pub const METER_SPEED: f32 = 6.0;
+
+///Component for the chromatic aberration fx entities of a special mode, so they can fade as the special moves get used up
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ModeFx {
+    ///The alpha the lighting gave the entity, before fading
+    pub light: f32,
+    ///The alpha that was set after fading, to spot when the lighting changes it again
+    pub last_written: f32,
+}
+impl ModeFx {
+    ///Gets the faded alpha for the entity
+    ///
+    ///  - **current** is the alpha the entity has right now
+    ///  - **fraction** is the fraction of special moves left
+    pub fn fade(&mut self, current: f32, fraction: f32) -> f32 {
+        #[allow(clippy::float_cmp)]
+        if current != self.last_written {
+            self.light = current;
+        }
+
+        self.last_written = self.light * fraction;
+        self.last_written
+    }
+}
+impl Component for ModeFx {
+    type Storage = DenseVecStorage<Self>;
+}
+
+///Component for the HUD bar showing how many special moves are left
+#[derive(Copy, Clone, Debug)]
+pub struct BudgetMeter {
+    ///The fraction of the bar currently shown
+    pub shown: f32,
+}
+impl Default for BudgetMeter {
+    fn default() -> Self {
+        Self { shown: 1.0 }
+    }
+}
+impl BudgetMeter {
+    ///Moves the shown fraction towards the actual fraction of moves left, and returns the new shown fraction
+    ///
+    ///  - **target** is the fraction of special moves left
+    ///  - **delta** is the time since the last frame, in seconds
+    pub fn step(&mut self, target: f32, delta: f32) -> f32 {
+        self.shown += (target - self.shown) * (delta * METER_SPEED).min(1.0);
+        if (target - self.shown).abs() < 0.001 {
+            self.shown = target;
+        }
+        self.shown
+    }
+}
+impl Component for BudgetMeter {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn fade_test() {
+        let mut fx = ModeFx::default();
+        assert_eq!(fx.fade(0.8, 0.5), 0.4);
+        assert_eq!(fx.fade(0.4, 0.25), 0.2, "Keeps the lighting alpha");
+        assert_eq!(fx.fade(1.0, 0.25), 0.25, "Picks up new lighting");
+    }
+
+    #[test]
+    pub fn meter_test() {
+        let mut meter = BudgetMeter::default();
+        let first = meter.step(0.5, 0.05);
+        assert!(first < 1.0 && first > 0.5, "Animates rather than jumping");
+
+        for _ in 0..100 {
+            meter.step(0.5, 0.05);
+        }
+        assert_eq!(meter.shown, 0.5);
+        assert_eq!(meter.step(0.0, 1.0), 0.0);
+    }
+}
@@ -1,5 +1,4 @@
-use crate::tile_transform::TileTransform;
-use amethyst::core::ecs::{Component, DefaultVecStorage};
+use amethyst::core::ecs::{Component, DefaultVecStorage, NullStorage};
 use lonely_tribes_tags::trigger_type::TriggerType;
 
 ///Component to mark an entity as a collider
@@ -30,43 +29,9 @@ impl Component for Collider {
     type Storage = DefaultVecStorage<Self>;
 }
 
-///Struct for holding a list of all colliders, and triggers
-pub struct ColliderList {
-    ///List of all colliders
-    colls: Vec<TileTransform>,
-    ///List of all Triggers
-    triggers: Vec<(TileTransform, TriggerType)>,
-}
-impl ColliderList {
-    ///Constructor for ColliderList
-    /// Initialises both lists inside as empty vectors
-    pub fn new() -> Self {
-        Self {
-            colls: Vec::new(),
-            triggers: Vec::new(),
-        }
-    }
-
-    ///Sets the list of colliders
-    pub fn set(&mut self, c: Vec<TileTransform>) {
-        self.colls = c;
-    }
-    ///Sets the list of triggers
-    pub fn set_triggers(&mut self, t: Vec<(TileTransform, TriggerType)>) {
-        self.triggers = t;
-    }
-
-    ///Gets a clone of the list of colliders
-    pub fn get(&self) -> &Vec<TileTransform> {
-        &self.colls
-    }
-    ///Gets a clone of the list of triggers
-    pub fn get_triggers(&self) -> &Vec<(TileTransform, TriggerType)> {
-        &self.triggers
-    }
-}
-impl Default for ColliderList {
-    fn default() -> Self {
-        Self::new()
-    }
+///Component to mark a collider as a block which tribes can push around
+#[derive(Default, Debug)]
+pub struct Pushable;
+impl Component for Pushable {
+    type Storage = NullStorage<Self>;
 }
@@ -0,0 +1,104 @@
+use crate::{tile_transform::TileTransform, win_related::GamePlayingMode};
+use amethyst::core::shrev::EventChannel;
+
+///Something that happened in the game
+///
+///These get sent down an `EventChannel<GameEvent>` resource, so anything can subscribe to them by registering a reader
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    ///A level was started
+    LevelStarted {
+        ///The path of the level
+        level: String,
+        ///The seed, if the level is procedurally generated
+        seed: Option<u32>,
+    },
+    ///A member of a tribe moved
+    TribeMoved {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where it moved from
+        from: TileTransform,
+        ///Where it moved to
+        to: TileTransform,
+    },
+    ///A member of a tribe tried to move, but walked into a collider or off the edge of the map
+    TribeBumped {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where it was
+        at: TileTransform,
+    },
+    ///A member of a tribe pushed a block
+    BoxPushed {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where the block was
+        from: TileTransform,
+        ///Where the block went
+        to: TileTransform,
+    },
+    ///A gate was opened or closed by a pressure plate or switch
+    GateToggled {
+        ///Where the gate is
+        at: TileTransform,
+        ///Whether or not it is now open
+        open: bool,
+    },
+    ///A different tribe was selected, in a level where tribes are moved one at a time
+    TribeSelected {
+        ///The id of the tribe
+        tribe: usize,
+    },
+    ///A member of a tribe walked into a hazard or an NPC, or was attacked by a boss, and was lost
+    TribeMemberLost {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where it was lost
+        at: TileTransform,
+    },
+    ///A member of a tribe walked into the boss and damaged it, and was used up doing so
+    BossHit {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where the boss is
+        at: TileTransform,
+        ///How much damage was done
+        damage: u32,
+        ///How much health the boss has left
+        hp_left: u32,
+    },
+    ///A new member of a tribe appeared at a respawn point
+    TribeRespawned {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where it appeared
+        at: TileTransform,
+    },
+    ///Two members of the same tribe ended up on the same tile
+    TribesMerged {
+        ///The id of the tribe
+        tribe: usize,
+        ///Where they merged
+        at: TileTransform,
+    },
+    ///The GamePlayingMode changed
+    ModeChanged {
+        ///The mode before
+        from: GamePlayingMode,
+        ///The mode after
+        to: GamePlayingMode,
+    },
+    ///Every tribe was merged, or the boss was defeated, so the level was won
+    LevelWon,
+    ///Two different tribes met, a tribe was wiped out or the boss won, so the level was lost
+    LevelLost {
+        ///Where they met
+        at: TileTransform,
+    },
+    ///A message was shown to the player
+    MessagePosted(String),
+}
+
+///Type alias for the channel which all GameEvents are sent down
+pub type GameEventChannel = EventChannel<GameEvent>;
@@ -0,0 +1,18 @@
+use crate::tile_transform::TileTransform;
+use amethyst::core::ecs::{Component, DenseVecStorage};
+
+///Special floors which move tribe members again after they step onto them
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FloorEffect {
+    ///Sends tribe members to the other teleporter with the same link id
+    Teleporter(usize),
+    ///Pushes tribe members one tile along, eg. a conveyor belt
+    OneWay(TileTransform),
+}
+
+///Component for a special floor tile
+#[derive(Copy, Clone, Debug)]
+pub struct Floor(pub FloorEffect);
+impl Component for Floor {
+    type Storage = DenseVecStorage<Self>;
+}
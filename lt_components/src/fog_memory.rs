@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+///How much of the map the fog of war remembers once it has been seen - set per level
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FogMemory {
+    ///Tiles go back to black as soon as they aren't lit
+    None,
+    ///Tiles which have been seen stay at a dim tint
+    Layout,
+    ///Every tile starts off at the dim tint
+    FullReveal,
+}
+impl Default for FogMemory {
+    fn default() -> Self {
+        Self::None
+    }
+}
@@ -0,0 +1,133 @@
+use amethyst::core::ecs::{Component, DenseVecStorage};
+
+///The different kinds of hazard tile
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HazardKind {
+    ///Any tribe member that walks in falls and is lost
+    Pit,
+    ///Any tribe member that walks onto them loses the level
+    Spikes,
+    ///Any tribe member that walks in drowns and is lost
+    Water,
+}
+impl HazardKind {
+    ///Whether or not stepping on this hazard loses the level straight away, rather than just losing the tribe member
+    pub fn loses_level(&self) -> bool {
+        self == &Self::Spikes
+    }
+}
+
+///Timer for a hazard which toggles on and off, driven by the number of moves in the level
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct HazardTimer {
+    ///How many moves between each toggle
+    pub every: u32,
+    ///How many moves to offset the timer by, so that hazards can be out of step with each other
+    pub offset: u32,
+}
+impl HazardTimer {
+    ///Checks whether or not the hazard is active after a number of moves
+    ///
+    ///Hazards start active, then toggle every `every` moves - a timer which toggles every 0 moves is always active
+    ///
+    ///  - **moves** is how many moves have been made in the level
+    pub fn is_active(&self, moves: i32) -> bool {
+        if self.every == 0 {
+            return true;
+        }
+
+        let moves = moves.max(0) as u32 + self.offset;
+        (moves / self.every) % 2 == 0
+    }
+}
+
+///Component for a hazard tile
+#[derive(Copy, Clone, Debug)]
+pub struct Hazard {
+    ///What kind of hazard it is
+    pub kind: HazardKind,
+    ///The timer, if the hazard toggles on and off
+    pub timer: Option<HazardTimer>,
+    ///Whether or not it is currently active
+    pub active: bool,
+    ///The sprite indices for when it is inactive, and when it is active
+    pub sprites: [usize; 2],
+}
+impl Hazard {
+    ///Constructor for an active Hazard
+    ///
+    ///  - **kind** is what kind of hazard it is
+    ///  - **sprites** is the sprite indices for when it is inactive, and when it is active
+    pub fn new(kind: HazardKind, sprites: [usize; 2]) -> Self {
+        Self {
+            kind,
+            timer: None,
+            active: true,
+            sprites,
+        }
+    }
+
+    ///Gets the sprite index for the current state
+    pub fn sprite(&self) -> usize {
+        self.sprites[self.active as usize]
+    }
+
+    ///Updates whether or not the hazard is active, and returns whether or not it changed
+    ///
+    ///  - **moves** is how many moves have been made in the level
+    pub fn update(&mut self, moves: i32) -> bool {
+        let nu = self.timer.map_or(true, |timer| timer.is_active(moves));
+        let changed = nu != self.active;
+        self.active = nu;
+        changed
+    }
+}
+impl Component for Hazard {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn timer_test() {
+        let timer = HazardTimer {
+            every: 2,
+            offset: 0,
+        };
+        let got: Vec<bool> = (0..6).map(|m| timer.is_active(m)).collect();
+        assert_eq!(got, vec![true, true, false, false, true, true]);
+
+        let offset = HazardTimer {
+            every: 2,
+            offset: 1,
+        };
+        let got: Vec<bool> = (0..4).map(|m| offset.is_active(m)).collect();
+        assert_eq!(got, vec![true, false, false, true]);
+
+        assert!(HazardTimer {
+            every: 0,
+            offset: 0
+        }
+        .is_active(7));
+    }
+
+    #[test]
+    pub fn hazard_update_test() {
+        let mut spikes = Hazard {
+            timer: Some(HazardTimer {
+                every: 1,
+                offset: 0,
+            }),
+            ..Hazard::new(HazardKind::Spikes, [0, 1])
+        };
+        assert!(!spikes.update(0));
+        assert!(spikes.update(1));
+        assert_eq!(spikes.sprite(), 0);
+
+        let mut pit = Hazard::new(HazardKind::Pit, [0, 1]);
+        assert!(!pit.update(5));
+        assert!(pit.active);
+    }
+}
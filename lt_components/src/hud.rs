@@ -0,0 +1,260 @@
+use crate::{
+    player::{Player, TribeControl},
+    special_modes::SpecialMode,
+    win_related::{GameModeManager, GamePlayingMode},
+};
+use amethyst::core::ecs::{Component, DenseVecStorage};
+use std::collections::BTreeMap;
+
+///Which part of the HUD a UiText shows
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HudElement {
+    ///How many moves have been made this level
+    Moves,
+    ///The key for the current special mode, coloured like its effects
+    ModeIcon,
+    ///How many special moves are left, along with which modes can be used
+    Specials,
+    ///How many members each tribe has left
+    Tribes,
+    ///Text which is set when the level starts and never changes, like the level name
+    Fixed,
+}
+
+///Component for UiText which is part of the HUD
+#[derive(Clone, Debug)]
+pub struct HudText {
+    ///Which part of the HUD this is
+    pub element: HudElement,
+    ///The values the text was last made from - if None, the text hasn't been made yet
+    pub shown: Option<HudValues>,
+}
+impl HudText {
+    ///Constructor for HudText, which will get its text on the next frame
+    pub fn new(element: HudElement) -> Self {
+        Self {
+            element,
+            shown: None,
+        }
+    }
+}
+impl Component for HudText {
+    type Storage = DenseVecStorage<Self>;
+}
+
+///Every value that the HUD shows, so that text only gets remade when one of them changes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HudValues {
+    ///How many moves have been made
+    pub moves: i32,
+    ///The current special mode
+    pub mode: GamePlayingMode,
+    ///How many moves are left in the shared pool, out of how many
+    pub specials: (i32, i32),
+    ///Every mode that can be used, with its cost and moves left
+    pub available: Vec<(GamePlayingMode, i32, i32)>,
+    ///Every tribe, as (tribe id, members left, size of the biggest merged member)
+    pub tribes: Vec<(usize, usize, u16)>,
+    ///How many moves each tribe has made, in the same order as tribes - empty if tribes move in lock-step
+    pub tribe_moves: Vec<i32>,
+}
+impl HudValues {
+    ///Gets the values from the current level
+    ///
+    ///  - **moves** is how many moves have been made
+    ///  - **gmm** is the GameModeManager for the level
+    ///  - **control** is the TribeControl, for how many moves each tribe has made
+    ///  - **players** is every tribe member still in the level
+    pub fn new<'a>(
+        moves: i32,
+        gmm: &GameModeManager,
+        control: &TribeControl,
+        players: impl Iterator<Item = &'a Player>,
+    ) -> Self {
+        let tribes = tribe_counts(players);
+        let tribe_moves = if control.independent {
+            tribes.iter().map(|(id, _, _)| control.moves(*id)).collect()
+        } else {
+            vec![]
+        };
+
+        Self {
+            moves,
+            mode: gmm.current_mode,
+            specials: (gmm.moves_left, gmm.total_moves),
+            available: gmm.available_modes(),
+            tribes,
+            tribe_moves,
+        }
+    }
+
+    ///Gets the text for a part of the HUD - None for Fixed, which never changes
+    ///
+    ///  - **element** is which part of the HUD
+    ///  - **gmm** is the GameModeManager, for the keys for each mode
+    pub fn text(&self, element: HudElement, gmm: &GameModeManager) -> Option<String> {
+        match element {
+            HudElement::Moves => Some(format!("Moves: {}", self.moves)),
+            HudElement::ModeIcon => Some(mode_icon(gmm.modes.get(self.mode)).0),
+            HudElement::Specials => {
+                let (left, total) = self.specials;
+                Some(format!(
+                    "SPECIALS: {}/{}\n{}",
+                    left,
+                    total,
+                    available_modes_text(gmm, &self.available)
+                ))
+            }
+            HudElement::Tribes => Some(tribes_text(&self.tribes, &self.tribe_moves)),
+            HudElement::Fixed => None,
+        }
+    }
+}
+
+///Counts up the members in each tribe, sorted by tribe id
+///
+///Returns (tribe id, members left, size of the biggest merged member)
+pub fn tribe_counts<'a>(players: impl Iterator<Item = &'a Player>) -> Vec<(usize, usize, u16)> {
+    let mut counts: BTreeMap<usize, (usize, u16)> = BTreeMap::new();
+    for player in players {
+        let (members, biggest) = counts.entry(player.id).or_insert((0, 0));
+        *members += 1;
+        *biggest = (*biggest).max(player.no_players + 1);
+    }
+
+    counts
+        .into_iter()
+        .map(|(id, (members, biggest))| (id, members, biggest))
+        .collect()
+}
+
+///Gets the text listing how many members each tribe has left
+///
+///  - **tribes** is each tribe, from `tribe_counts`
+///  - **tribe_moves** is how many moves each tribe has made, in the same order - if empty, no move counts are shown
+pub fn tribes_text(tribes: &[(usize, usize, u16)], tribe_moves: &[i32]) -> String {
+    let lines: Vec<String> = tribes
+        .iter()
+        .enumerate()
+        .map(|(i, (id, members, biggest))| {
+            let mut line = format!("Tribe {}: {} left", id, members);
+            if *biggest > 1 {
+                line.push_str(&format!(" (biggest x{})", biggest));
+            }
+            if let Some(moves) = tribe_moves.get(i) {
+                line.push_str(&format!(", {} moves", moves));
+            }
+            line
+        })
+        .collect();
+    lines.join("\n")
+}
+
+///Gets the text for the best score and par of a level, with dashes for ones that don't exist
+pub fn best_and_par_text(best: Option<i32>, par: Option<i32>) -> String {
+    let or_dash = |n: Option<i32>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+    format!("Best: {} | Par: {}", or_dash(best), or_dash(par))
+}
+
+///Gets the icon for a special mode - the key to use it, coloured like its effects
+pub fn mode_icon(mode: &dyn SpecialMode) -> (String, [f32; 4]) {
+    let key = mode
+        .key()
+        .map_or_else(|| "-".to_string(), |key| format!("{:?}", key));
+    let colour = mode.fx_layers().first().map_or([1.0; 4], |layer| {
+        let [r, g, b, _] = layer.colour;
+        [r, g, b, 1.0]
+    });
+    (key, colour)
+}
+
+///Gets the text listing which special modes can be used, along with their keys and costs
+///
+///Modes with their own pool of moves also show how many are left in it
+///
+///  - **available** is the modes, with their costs and moves left, from `GameModeManager::available_modes`
+pub fn available_modes_text(
+    gmm: &GameModeManager,
+    available: &[(GamePlayingMode, i32, i32)],
+) -> String {
+    if available.is_empty() {
+        return "No SPECIAL modes left".to_string();
+    }
+
+    let lines: Vec<String> = available
+        .iter()
+        .map(|(mode, cost, left)| {
+            let key = mode_icon(gmm.modes.get(*mode)).0;
+            if gmm.budgets.contains_key(mode) {
+                format!("[{}] {:?} - costs {}, {} left", key, mode, cost, left)
+            } else {
+                format!("[{}] {:?} - costs {}", key, mode, cost)
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::GameEventChannel;
+
+    #[test]
+    pub fn tribe_counts_test() {
+        let mut merged = Player::new(1);
+        merged.no_players = 2;
+        let players = vec![Player::new(1), Player::new(0), merged, Player::new(1)];
+
+        let counts = tribe_counts(players.iter());
+        assert_eq!(counts, vec![(0, 1, 1), (1, 3, 3)]);
+        assert_eq!(
+            tribes_text(&counts, &[]),
+            "Tribe 0: 1 left\nTribe 1: 3 left (biggest x3)"
+        );
+        assert_eq!(
+            tribes_text(&counts, &[4, 0]),
+            "Tribe 0: 1 left, 4 moves\nTribe 1: 3 left (biggest x3), 0 moves"
+        );
+    }
+
+    #[test]
+    pub fn tribe_moves_test() {
+        let players = vec![Player::new(0), Player::new(1)];
+        let gmm = GameModeManager::with_rules(0, None, &[]);
+        let mut control = TribeControl::new(false);
+        control.add_move(1);
+        assert!(HudValues::new(1, &gmm, &control, players.iter())
+            .tribe_moves
+            .is_empty());
+
+        control.independent = true;
+        let values = HudValues::new(1, &gmm, &control, players.iter());
+        assert_eq!(values.tribe_moves, vec![0, 1]);
+    }
+
+    #[test]
+    pub fn values_test() {
+        let mut events = GameEventChannel::new();
+        let mut gmm = GameModeManager::with_rules(4, Some(vec![GamePlayingMode::Nudger]), &[]);
+        let players = vec![Player::new(0)];
+
+        let control = TribeControl::default();
+
+        let before = HudValues::new(0, &gmm, &control, players.iter());
+        assert_eq!(before, HudValues::new(0, &gmm, &control, players.iter()));
+        assert_eq!(
+            before.text(HudElement::Specials, &gmm),
+            Some("SPECIALS: 4/4\n[N] Nudger - costs 2".to_string())
+        );
+        assert_eq!(
+            before.text(HudElement::ModeIcon, &gmm),
+            Some("B".to_string())
+        );
+        assert_eq!(before.text(HudElement::Fixed, &gmm), None);
+
+        gmm.set_mode(GamePlayingMode::Nudger, &mut events);
+        assert_ne!(before, HudValues::new(0, &gmm, &control, players.iter()));
+        assert_eq!(best_and_par_text(Some(12), None), "Best: 12 | Par: -");
+    }
+}
@@ -1,8 +1,20 @@
+pub mod boss;
+pub mod budget_meter;
 pub mod colliders;
 pub mod data_holder;
+pub mod events;
+pub mod floors;
+pub mod fog_memory;
+pub mod hazards;
+pub mod hud;
+pub mod npcs;
+pub mod occupancy;
 pub mod player;
 pub mod point_light;
-pub mod score;
+pub mod screen_scaled;
+pub mod script;
+pub mod special_modes;
+pub mod switches;
 pub mod text_wobble;
 pub mod tile_transform;
 pub mod win_related;
@@ -0,0 +1,146 @@
+use crate::tile_transform::TileTransform;
+use amethyst::core::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
+
+///The different ways an NPC can move - NPCs take one step after each player move
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NpcPattern {
+    ///Walks to each (x, y) waypoint in turn, then loops back round to the first
+    Patrol(Vec<(i32, i32)>),
+    ///Walks towards the nearest tribe member
+    Chase,
+    ///Walks the opposite way to the player's input, like a reflection
+    Mirror,
+}
+
+///Gets a single step from one tile towards another, going along whichever axis is furthest first (horizontally if tied)
+///
+///  - **from** is where the step starts
+///  - **to** is where the step is heading
+pub fn step_towards(from: TileTransform, to: TileTransform) -> TileTransform {
+    let diff = to - from;
+    if diff.x == 0 && diff.y == 0 {
+        TileTransform::default()
+    } else if diff.x.abs() >= diff.y.abs() {
+        TileTransform::new(diff.x.signum(), 0)
+    } else {
+        TileTransform::new(0, diff.y.signum())
+    }
+}
+
+///Component for an NPC, which moves using an authored pattern and gets rid of any tribe member it touches
+#[derive(Clone, Debug)]
+pub struct Npc {
+    ///How the NPC moves
+    pub pattern: NpcPattern,
+    ///The index of the waypoint a patrolling NPC is heading for
+    next_waypoint: usize,
+}
+impl Npc {
+    ///Constructor for an NPC
+    ///
+    ///  - **pattern** is how the NPC moves
+    pub fn new(pattern: NpcPattern) -> Self {
+        Self {
+            pattern,
+            next_waypoint: 0,
+        }
+    }
+
+    ///Gets the tile the NPC wants to step onto next - this doesn't check whether or not the tile is free
+    ///
+    ///  - **pos** is where the NPC currently is
+    ///  - **input** is the direction the player just moved in, as a single step
+    ///  - **tribe_members** is where every tribe member is
+    pub fn proposed_tile(
+        &mut self,
+        pos: TileTransform,
+        input: TileTransform,
+        tribe_members: &[TileTransform],
+    ) -> TileTransform {
+        let step = match &self.pattern {
+            NpcPattern::Patrol(waypoints) => {
+                if waypoints.is_empty() {
+                    return pos;
+                }
+
+                let mut target =
+                    TileTransform::from(waypoints[self.next_waypoint % waypoints.len()]);
+                if target == pos {
+                    self.next_waypoint = (self.next_waypoint + 1) % waypoints.len();
+                    target = waypoints[self.next_waypoint].into();
+                }
+                step_towards(pos, target)
+            }
+            NpcPattern::Chase => {
+                let mut nearest: Option<(TileTransform, f32)> = None;
+                for t in tribe_members {
+                    let dist = pos.distance(t);
+                    if nearest.map_or(true, |(_, best)| dist < best) {
+                        nearest = Some((*t, dist));
+                    }
+                }
+
+                nearest.map_or_else(TileTransform::default, |(t, _)| step_towards(pos, t))
+            }
+            NpcPattern::Mirror => TileTransform::new(-input.x.signum(), -input.y.signum()),
+        };
+
+        pos + step
+    }
+}
+impl Component for Npc {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn step_towards_test() {
+        let from = TileTransform::new(2, 2);
+        assert_eq!(step_towards(from, (5, 3).into()), TileTransform::new(1, 0));
+        assert_eq!(
+            step_towards(from, (1, -4).into()),
+            TileTransform::new(0, -1)
+        );
+        assert_eq!(step_towards(from, (4, 4).into()), TileTransform::new(1, 0));
+        assert_eq!(step_towards(from, from), TileTransform::default());
+    }
+
+    #[test]
+    pub fn patrol_test() {
+        let mut npc = Npc::new(NpcPattern::Patrol(vec![(0, 0), (2, 0)]));
+        let mut pos = TileTransform::new(0, 0);
+        let mut path = Vec::new();
+        for _ in 0..5 {
+            pos = npc.proposed_tile(pos, TileTransform::default(), &[]);
+            path.push(pos.x);
+        }
+        assert_eq!(path, vec![1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    pub fn chase_and_mirror_test() {
+        let pos = TileTransform::new(5, 5);
+        let tribes = [TileTransform::new(9, 5), TileTransform::new(5, 3)];
+
+        let mut chaser = Npc::new(NpcPattern::Chase);
+        assert_eq!(
+            chaser.proposed_tile(pos, TileTransform::default(), &tribes),
+            TileTransform::new(5, 4)
+        );
+        assert_eq!(
+            chaser.proposed_tile(pos, TileTransform::default(), &[]),
+            pos,
+            "Nothing to chase"
+        );
+
+        let mut mirror = Npc::new(NpcPattern::Mirror);
+        assert_eq!(
+            mirror.proposed_tile(pos, TileTransform::new(0, 3), &tribes),
+            TileTransform::new(5, 4)
+        );
+    }
+}
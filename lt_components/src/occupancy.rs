@@ -0,0 +1,344 @@
+use crate::{floors::FloorEffect, tile_transform::TileTransform};
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+use lonely_tribes_tags::trigger_type::TriggerType;
+use std::collections::HashMap;
+
+///Gets the index of a tile in a dense `WIDTH * HEIGHT` grid, or None if it is off the map
+pub fn tile_index(t: &TileTransform) -> Option<usize> {
+    if t.x < 0 || t.x >= WIDTH || t.y < 0 || t.y >= HEIGHT {
+        None
+    } else {
+        Some((t.x + t.y * WIDTH) as usize)
+    }
+}
+
+///Gets the tile for an index into a dense `WIDTH * HEIGHT` grid
+pub fn index_to_tile(i: usize) -> TileTransform {
+    TileTransform::new(i as i32 % WIDTH, i as i32 / WIDTH)
+}
+
+///A dense grid holding one value for every tile on the map
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileGrid<T> {
+    cells: Vec<T>,
+}
+impl<T: Clone> TileGrid<T> {
+    ///Constructor for TileGrid, with every tile set to the same value
+    pub fn new(value: T) -> Self {
+        Self {
+            cells: vec![value; (WIDTH * HEIGHT) as usize],
+        }
+    }
+}
+impl<T: Clone + Default> Default for TileGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+impl<T> TileGrid<T> {
+    ///Gets the value for a tile, or None if it is off the map
+    pub fn get(&self, t: &TileTransform) -> Option<&T> {
+        tile_index(t).map(|i| &self.cells[i])
+    }
+
+    ///Gets a mutable reference to the value for a tile, or None if it is off the map
+    pub fn get_mut(&mut self, t: &TileTransform) -> Option<&mut T> {
+        tile_index(t).map(move |i| &mut self.cells[i])
+    }
+
+    ///Sets the value for a tile - does nothing if it is off the map
+    pub fn set(&mut self, t: &TileTransform, value: T) {
+        if let Some(cell) = self.get_mut(t) {
+            *cell = value;
+        }
+    }
+
+    ///Gets every value, indexed by `x + y * WIDTH`
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+}
+impl TileGrid<bool> {
+    ///Constructor for a grid where only the given tiles are true
+    pub fn from_tiles(tiles: &[TileTransform]) -> Self {
+        let mut grid = Self::new(false);
+        for t in tiles {
+            grid.set(t, true);
+        }
+        grid
+    }
+
+    ///Checks whether a tile is true - tiles off the map are false
+    pub fn contains(&self, t: &TileTransform) -> bool {
+        self.get(t).copied().unwrap_or(false)
+    }
+}
+
+///Resource holding what is on every tile - colliders, triggers and tribe members
+///
+///It gets rebuilt from scratch by the `ListSystem` when it is invalidated (eg. when a level is loaded), and otherwise gets updated as things move
+#[derive(Clone, Debug)]
+pub struct OccupancyGrid {
+    ///Whether or not there is a collider on each tile
+    colliders: TileGrid<bool>,
+    ///The trigger on each tile, not including tribe members
+    triggers: TileGrid<Option<TriggerType>>,
+    ///The tribe ids of every member on each tile
+    players: TileGrid<Vec<usize>>,
+    ///Whether or not there is a pushable block on each tile
+    pushables: TileGrid<bool>,
+    ///The special floor on each tile
+    floors: TileGrid<Option<FloorEffect>>,
+    ///Where every teleporter is, indexed by link id
+    teleporters: HashMap<usize, Vec<TileTransform>>,
+    ///Whether or not the grid needs rebuilding from the components
+    needs_rebuild: bool,
+}
+impl Default for OccupancyGrid {
+    fn default() -> Self {
+        Self {
+            colliders: TileGrid::new(false),
+            triggers: TileGrid::new(None),
+            players: TileGrid::default(),
+            pushables: TileGrid::new(false),
+            floors: TileGrid::new(None),
+            teleporters: HashMap::new(),
+            needs_rebuild: true,
+        }
+    }
+}
+
+impl OccupancyGrid {
+    ///Constructor for an empty OccupancyGrid, which doesn't need rebuilding
+    pub fn empty() -> Self {
+        Self {
+            needs_rebuild: false,
+            ..Default::default()
+        }
+    }
+
+    ///Whether or not the grid needs rebuilding from the components
+    pub fn needs_rebuild(&self) -> bool {
+        self.needs_rebuild
+    }
+
+    ///Marks the grid as needing to be rebuilt from the components
+    pub fn invalidate(&mut self) {
+        self.needs_rebuild = true;
+    }
+
+    ///Adds a collider to a tile
+    pub fn add_collider(&mut self, t: &TileTransform) {
+        self.colliders.set(t, true);
+    }
+    ///Adds a pushable block to a tile, which is also a collider
+    pub fn add_pushable(&mut self, t: &TileTransform) {
+        self.colliders.set(t, true);
+        self.pushables.set(t, true);
+    }
+    ///Moves a pushable block from one tile to another
+    pub fn move_pushable(&mut self, from: &TileTransform, to: &TileTransform) {
+        self.colliders.set(from, false);
+        self.pushables.set(from, false);
+        self.add_pushable(to);
+    }
+    ///Opens a gate, so it is a trigger instead of a collider
+    pub fn open_gate(&mut self, t: &TileTransform, link: usize) {
+        self.colliders.set(t, false);
+        self.triggers.set(t, Some(TriggerType::Gate(link)));
+    }
+    ///Closes a gate, so it is a collider again
+    pub fn close_gate(&mut self, t: &TileTransform) {
+        self.triggers.set(t, None);
+        self.colliders.set(t, true);
+    }
+    ///Adds a special floor to a tile
+    pub fn add_floor(&mut self, t: &TileTransform, effect: FloorEffect) {
+        if tile_index(t).is_none() {
+            return;
+        }
+
+        self.floors.set(t, Some(effect));
+        if let FloorEffect::Teleporter(link) = effect {
+            self.teleporters.entry(link).or_default().push(*t);
+        }
+    }
+    ///Adds a trigger to a tile
+    ///
+    ///Tribe members should be added with `add_player` instead
+    pub fn add_trigger(&mut self, t: &TileTransform, trigger: TriggerType) {
+        self.triggers.set(t, Some(trigger));
+    }
+    ///Adds a member of a tribe to a tile
+    pub fn add_player(&mut self, t: &TileTransform, tribe: usize) {
+        if let Some(list) = self.players.get_mut(t) {
+            list.push(tribe);
+        }
+    }
+    ///Removes a member of a tribe from a tile
+    pub fn remove_player(&mut self, t: &TileTransform, tribe: usize) {
+        if let Some(list) = self.players.get_mut(t) {
+            if let Some(index) = list.iter().position(|id| *id == tribe) {
+                list.swap_remove(index);
+            }
+        }
+    }
+    ///Moves a member of a tribe from one tile to another
+    pub fn move_player(&mut self, from: &TileTransform, to: &TileTransform, tribe: usize) {
+        self.remove_player(from, tribe);
+        self.add_player(to, tribe);
+    }
+
+    ///Checks whether there is a collider on a tile
+    pub fn is_collider(&self, t: &TileTransform) -> bool {
+        self.colliders.contains(t)
+    }
+    ///Checks whether there is a pushable block on a tile
+    pub fn is_pushable(&self, t: &TileTransform) -> bool {
+        self.pushables.contains(t)
+    }
+    ///Gets the trigger on a tile, not including tribe members
+    pub fn trigger(&self, t: &TileTransform) -> Option<TriggerType> {
+        self.triggers.get(t).copied().flatten()
+    }
+    ///Gets the special floor on a tile
+    pub fn floor(&self, t: &TileTransform) -> Option<FloorEffect> {
+        self.floors.get(t).copied().flatten()
+    }
+    ///Gets where a teleporter sends tribe members - the first other teleporter with the same link id
+    pub fn teleporter_exit(&self, t: &TileTransform) -> Option<TileTransform> {
+        match self.floor(t) {
+            Some(FloorEffect::Teleporter(link)) => self
+                .teleporters
+                .get(&link)
+                .and_then(|list| list.iter().find(|exit| *exit != t))
+                .copied(),
+            _ => None,
+        }
+    }
+    ///Gets the tribe ids of every member on a tile
+    pub fn players(&self, t: &TileTransform) -> &[usize] {
+        match self.players.get(t) {
+            Some(list) => list,
+            None => &[],
+        }
+    }
+    ///Checks whether there is any trigger on a tile, including tribe members
+    pub fn has_trigger(&self, t: &TileTransform) -> bool {
+        self.trigger(t).is_some() || !self.players(t).is_empty()
+    }
+
+    ///Checks whether there is a tribe member or pushable block on a tile, eg. to hold down a pressure plate
+    pub fn is_weighed_down(&self, t: &TileTransform) -> bool {
+        !self.players(t).is_empty() || self.is_pushable(t)
+    }
+
+    ///Checks whether a tile can't be moved onto
+    ///
+    ///Tiles off the map are always blocked
+    ///
+    ///  - **t** is the tile
+    ///  - **triggers_block** is whether or not triggers (including tribe members) count as colliders
+    pub fn is_blocked(&self, t: &TileTransform, triggers_block: bool) -> bool {
+        tile_index(t).is_none() || self.is_collider(t) || (triggers_block && self.has_trigger(t))
+    }
+
+    ///Gets the grid of which tiles have colliders on them
+    pub fn colliders(&self) -> &TileGrid<bool> {
+        &self.colliders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn grid_test() {
+        let mut grid = TileGrid::new(0);
+        let t = TileTransform::new(3, 7);
+        grid.set(&t, 5);
+        grid.set(&TileTransform::new(-1, 0), 9);
+
+        assert_eq!(grid.get(&t), Some(&5));
+        assert_eq!(grid.get(&TileTransform::new(WIDTH, 0)), None);
+        assert_eq!(grid.as_slice().iter().sum::<i32>(), 5);
+        assert_eq!(index_to_tile(tile_index(&t).unwrap_or_default()), t);
+    }
+
+    #[test]
+    pub fn occupancy_test() {
+        let mut occupancy = OccupancyGrid::empty();
+        let wall = TileTransform::new(1, 1);
+        let door = TileTransform::new(2, 1);
+        let player = TileTransform::new(3, 1);
+
+        occupancy.add_collider(&wall);
+        occupancy.add_trigger(&door, TriggerType::Door);
+        occupancy.add_player(&player, 0);
+
+        assert!(occupancy.is_blocked(&wall, false));
+        assert!(!occupancy.is_blocked(&door, false));
+        assert!(occupancy.is_blocked(&door, true));
+        assert!(occupancy.is_blocked(&player, true));
+        assert!(occupancy.is_blocked(&TileTransform::new(-1, 1), false));
+
+        let moved = TileTransform::new(4, 1);
+        occupancy.move_player(&player, &moved, 0);
+        assert!(occupancy.players(&player).is_empty());
+        assert_eq!(occupancy.players(&moved), &[0]);
+    }
+
+    #[test]
+    pub fn pushable_test() {
+        let mut occupancy = OccupancyGrid::empty();
+        let from = TileTransform::new(5, 5);
+        let to = TileTransform::new(6, 5);
+
+        occupancy.add_pushable(&from);
+        assert!(occupancy.is_pushable(&from));
+        assert!(occupancy.is_blocked(&from, false));
+
+        occupancy.move_pushable(&from, &to);
+        assert!(!occupancy.is_blocked(&from, false));
+        assert!(occupancy.is_pushable(&to));
+        assert!(occupancy.is_collider(&to));
+        assert!(occupancy.is_weighed_down(&to));
+    }
+
+    #[test]
+    pub fn floors_test() {
+        let mut occupancy = OccupancyGrid::empty();
+        let a = TileTransform::new(1, 1);
+        let b = TileTransform::new(9, 4);
+        let lonely = TileTransform::new(5, 5);
+        let arrow = TileTransform::new(2, 2);
+
+        occupancy.add_floor(&a, FloorEffect::Teleporter(0));
+        occupancy.add_floor(&b, FloorEffect::Teleporter(0));
+        occupancy.add_floor(&lonely, FloorEffect::Teleporter(1));
+        occupancy.add_floor(&arrow, FloorEffect::OneWay(TileTransform::new(0, 1)));
+
+        assert_eq!(occupancy.teleporter_exit(&a), Some(b));
+        assert_eq!(occupancy.teleporter_exit(&b), Some(a));
+        assert_eq!(occupancy.teleporter_exit(&lonely), None);
+        assert_eq!(occupancy.teleporter_exit(&arrow), None);
+        assert_eq!(
+            occupancy.floor(&arrow),
+            Some(FloorEffect::OneWay(TileTransform::new(0, 1)))
+        );
+    }
+
+    #[test]
+    pub fn gate_test() {
+        let mut occupancy = OccupancyGrid::empty();
+        let gate = TileTransform::new(2, 3);
+
+        occupancy.close_gate(&gate);
+        assert!(occupancy.is_blocked(&gate, false));
+
+        occupancy.open_gate(&gate, 4);
+        assert!(!occupancy.is_blocked(&gate, false));
+        assert_eq!(occupancy.trigger(&gate), Some(TriggerType::Gate(4)));
+    }
+}
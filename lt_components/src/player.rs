@@ -1,4 +1,5 @@
 use amethyst::core::ecs::{Component, DefaultVecStorage};
+use std::collections::HashMap;
 
 ///Struct for Player
 #[derive(Default, Copy, Clone, Debug)]
@@ -15,7 +16,121 @@ impl Player {
     pub fn new(id: usize) -> Self {
         Self { id, no_players: 0 }
     }
+
+    ///Merges another member into this one - this one gains the other member, along with everyone already merged into it
+    ///
+    ///  - **other** is the member being merged in
+    pub fn merge(&mut self, other: &Player) {
+        self.no_players = self
+            .no_players
+            .saturating_add(other.no_players)
+            .saturating_add(1);
+    }
 }
 impl Component for Player {
     type Storage = DefaultVecStorage<Self>;
 }
+
+///Resource for which tribes get moved by each key press
+///
+///By default every tribe moves in lock-step, but levels can let the player pick one tribe at a time
+#[derive(Clone, Debug, Default)]
+pub struct TribeControl {
+    ///Whether or not tribes are moved one at a time - if false, every tribe moves in lock-step
+    pub independent: bool,
+    ///The id of the tribe which is currently selected
+    pub selected: usize,
+    ///How many moves each tribe has made, indexed by tribe id
+    moves: HashMap<usize, i32>,
+}
+impl TribeControl {
+    ///Constructor for TribeControl, with tribe 0 selected
+    ///
+    ///  - **independent** is whether or not tribes are moved one at a time
+    pub fn new(independent: bool) -> Self {
+        Self {
+            independent,
+            ..Default::default()
+        }
+    }
+
+    ///Whether or not a tribe gets moved by key presses
+    pub fn can_move(&self, tribe: usize) -> bool {
+        !self.independent || self.selected == tribe
+    }
+
+    ///Selects the next tribe after the current one, wrapping around
+    ///
+    ///  - **tribes** is the ids of every tribe still in the level
+    pub fn select_next(&mut self, tribes: &[usize]) {
+        let mut tribes = tribes.to_vec();
+        tribes.sort_unstable();
+        tribes.dedup();
+
+        if let Some(next) = tribes
+            .iter()
+            .find(|id| **id > self.selected)
+            .or_else(|| tribes.first())
+        {
+            self.selected = *next;
+        }
+    }
+
+    ///Adds a move to a tribe's count
+    pub fn add_move(&mut self, tribe: usize) {
+        *self.moves.entry(tribe).or_insert(0) += 1;
+    }
+
+    ///Gets how many moves a tribe has made
+    pub fn moves(&self, tribe: usize) -> i32 {
+        self.moves.get(&tribe).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn merge_test() {
+        let mut a = Player::new(0);
+        a.merge(&Player::new(0));
+        assert_eq!(a.no_players, 1);
+
+        let mut b = Player::new(0);
+        b.merge(&Player::new(0));
+        a.merge(&b);
+        assert_eq!(a.no_players, 3, "Two stacks of 2 make a stack of 4");
+    }
+
+    #[test]
+    pub fn lock_step_test() {
+        let control = TribeControl::new(false);
+        assert!(control.can_move(0));
+        assert!(control.can_move(3));
+    }
+
+    #[test]
+    pub fn select_next_test() {
+        let mut control = TribeControl::new(true);
+        assert!(control.can_move(0));
+        assert!(!control.can_move(2));
+
+        let tribes = [3, 0, 2, 3];
+        control.select_next(&tribes);
+        assert_eq!(control.selected, 2);
+        control.select_next(&tribes);
+        assert_eq!(control.selected, 3);
+        control.select_next(&tribes);
+        assert_eq!(control.selected, 0);
+    }
+
+    #[test]
+    pub fn moves_test() {
+        let mut control = TribeControl::new(true);
+        control.add_move(1);
+        control.add_move(1);
+        assert_eq!(control.moves(1), 2);
+        assert_eq!(control.moves(0), 0);
+    }
+}
@@ -1,25 +1,133 @@
+use crate::tile_transform::TileTransform;
 use amethyst::{
     core::ecs::{Component, DefaultVecStorage, DenseVecStorage},
     renderer::resources::Tint,
 };
 
+///How long each flicker lasts for, in seconds
+pub const FLICKER_INTERVAL: f32 = 0.15;
+
+///Colours for the light that each tribe casts, indexed by tribe id
+pub const TRIBE_LIGHT_COLOURS: [[f32; 3]; 4] = [
+    [1.0, 0.9, 0.75],
+    [0.75, 0.85, 1.0],
+    [0.8, 1.0, 0.8],
+    [1.0, 0.8, 0.95],
+];
+
+///How the brightness of a light drops off towards the edge of its radius
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LightFalloff {
+    ///Brightness drops off evenly
+    Linear,
+    ///Brightness drops off quickly near the light, then slowly
+    Quadratic,
+    ///Brightness stays high near the light, then drops off smoothly at the edge
+    Smooth,
+}
+impl Default for LightFalloff {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+impl LightFalloff {
+    ///Gets the brightness multiplier for a tile
+    ///
+    ///  - **dist** is how far away from the light the tile is
+    ///  - **rad** is the radius of the light
+    pub fn apply(&self, dist: f32, rad: f32) -> f32 {
+        if rad <= 0.0 {
+            return 0.0;
+        }
+
+        let t = ((rad - dist) / rad).max(0.0).min(1.0);
+        match self {
+            Self::Linear => t,
+            Self::Quadratic => t * t,
+            Self::Smooth => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
 ///Component for an entity to be a Point Light
 ///
 /// This works using amethyst Tint objects to 'shade' objects
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct PointLight {
     ///Radius of point light
     pub radius: u32,
+    ///Colour of the light, as RGB
+    pub colour: [f32; 3],
+    ///Brightness multiplier for the light
+    pub intensity: f32,
+    ///How the light drops off with distance
+    pub falloff: LightFalloff,
+    ///How much the light flickers - 0.0 is not at all, and 1.0 can go fully dark
+    pub flicker: f32,
 }
 impl PointLight {
-    ///Constructor
+    ///Constructor for a plain white light
     pub fn new(radius: u32) -> Self {
-        Self { radius }
+        Self {
+            radius,
+            ..Default::default()
+        }
+    }
+
+    ///Constructor for the light a tribe member carries
+    ///
+    ///  - **radius** is the radius of the light
+    ///  - **tribe** is the id of the tribe, which picks the colour
+    pub fn for_tribe(radius: u32, tribe: usize) -> Self {
+        Self {
+            radius,
+            colour: TRIBE_LIGHT_COLOURS[tribe % TRIBE_LIGHT_COLOURS.len()],
+            ..Default::default()
+        }
+    }
+
+    ///Gets how much this light contributes to a tile
+    ///
+    ///  - **dist** is how far away from the light the tile is
+    pub fn contribution(&self, dist: f32) -> f32 {
+        self.intensity * self.falloff.apply(dist, self.radius as f32)
+    }
+
+    ///Gets a copy of this light, with the intensity changed by its flicker
+    ///
+    ///The flicker only changes every `FLICKER_INTERVAL`, and lights in different places flicker differently
+    ///
+    ///  - **pos** is where the light is
+    ///  - **time** is the absolute time in seconds
+    pub fn flickered(&self, pos: TileTransform, time: f32) -> Self {
+        if self.flicker <= 0.0 {
+            return *self;
+        }
+
+        let step = (time / FLICKER_INTERVAL) as u32;
+        let mut h = step.wrapping_mul(0x9E37_79B9)
+            ^ (pos.x as u32).wrapping_mul(0x85EB_CA6B)
+            ^ (pos.y as u32).wrapping_mul(0xC2B2_AE35);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2C1B_3C6D);
+        h ^= h >> 12;
+        let noise = (h % 1000) as f32 / 1000.0;
+
+        Self {
+            intensity: self.intensity * (1.0 - self.flicker.min(1.0) * noise),
+            ..*self
+        }
     }
 }
 impl Default for PointLight {
     fn default() -> Self {
-        Self { radius: 1 }
+        Self {
+            radius: 1,
+            colour: [1.0; 3],
+            intensity: 1.0,
+            falloff: LightFalloff::default(),
+            flicker: 0.0,
+        }
     }
 }
 impl Component for PointLight {
@@ -34,3 +142,50 @@ pub struct TintOverride(pub Tint);
 impl Component for TintOverride {
     type Storage = DenseVecStorage<Self>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn falloff_test() {
+        for falloff in [
+            LightFalloff::Linear,
+            LightFalloff::Quadratic,
+            LightFalloff::Smooth,
+        ]
+        .iter()
+        {
+            assert_eq!(falloff.apply(0.0, 4.0), 1.0);
+            assert_eq!(falloff.apply(4.0, 4.0), 0.0);
+            assert_eq!(falloff.apply(10.0, 4.0), 0.0);
+        }
+
+        assert_eq!(LightFalloff::Linear.apply(2.0, 4.0), 0.5);
+        assert_eq!(LightFalloff::Quadratic.apply(2.0, 4.0), 0.25);
+        assert!(LightFalloff::Smooth.apply(1.0, 4.0) > LightFalloff::Linear.apply(1.0, 4.0));
+    }
+
+    #[test]
+    pub fn flicker_test() {
+        let steady = PointLight::new(3);
+        let pos = TileTransform::new(4, 5);
+        assert_eq!(steady.flickered(pos, 12.3), steady);
+
+        let torch = PointLight {
+            flicker: 0.5,
+            ..PointLight::new(3)
+        };
+        for i in 0..100 {
+            let time = (i as f32 + 0.3) * FLICKER_INTERVAL;
+            let intensity = torch.flickered(pos, time).intensity;
+            assert!(intensity >= 0.5 && intensity <= 1.0);
+            assert_eq!(
+                torch
+                    .flickered(pos, time + FLICKER_INTERVAL * 0.2)
+                    .intensity,
+                intensity
+            );
+        }
+    }
+}
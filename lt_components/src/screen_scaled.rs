@@ -0,0 +1,120 @@
+use amethyst::{
+    core::ecs::{Component, DenseVecStorage},
+    ui::{UiText, UiTransform},
+};
+
+///Component to hold the unscaled layout of a UiTransform, so that it can be re-scaled when the screen size changes
+#[derive(Copy, Clone, Debug)]
+pub struct ScreenScaled {
+    ///X position before scaling
+    pub x: f32,
+    ///Y position before scaling
+    pub y: f32,
+    ///Width before scaling
+    pub width: f32,
+    ///Height before scaling
+    pub height: f32,
+    ///Font size before scaling - if None, then the entity has no text
+    pub font_size: Option<f32>,
+    ///Whether or not the height should use the horizontal scaling factor - eg. for Sprites, which need to NOT scale in 2 dimensions
+    pub keep_square: bool,
+}
+
+impl ScreenScaled {
+    ///Constructor for ScreenScaled, which works out the unscaled layout from an already-scaled UiTransform
+    ///
+    ///  - **trans** is the UiTransform, after scaling
+    ///  - **font_size** is the font size of the text, after scaling, if there is text
+    ///  - **sf** is the scaling factor that was used
+    pub fn new(trans: &UiTransform, font_size: Option<f32>, (sf_x, sf_y): (f32, f32)) -> Self {
+        Self {
+            x: trans.local_x / sf_x,
+            y: trans.local_y / sf_y,
+            width: trans.width / sf_x,
+            height: trans.height / sf_y,
+            font_size: font_size.map(|f| f / sf_y),
+            keep_square: false,
+        }
+    }
+
+    ///Constructor for ScreenScaled for sprites, where the height was scaled using the horizontal scaling factor
+    pub fn new_square(trans: &UiTransform, (sf_x, sf_y): (f32, f32)) -> Self {
+        Self {
+            height: trans.height / sf_x,
+            keep_square: true,
+            ..Self::new(trans, None, (sf_x, sf_y))
+        }
+    }
+
+    ///Sets the UiTransform, and the UiText if there is one, to this layout at a given scaling factor
+    pub fn apply(
+        &self,
+        (sf_x, sf_y): (f32, f32),
+        trans: &mut UiTransform,
+        txt: Option<&mut UiText>,
+    ) {
+        trans.local_x = self.x * sf_x;
+        trans.local_y = self.y * sf_y;
+        trans.width = self.width * sf_x;
+        trans.height = if self.keep_square {
+            self.height * sf_x
+        } else {
+            self.height * sf_y
+        };
+
+        if let (Some(txt), Some(font_size)) = (txt, self.font_size) {
+            txt.font_size = font_size * sf_y;
+        }
+    }
+}
+
+impl Component for ScreenScaled {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::ui::Anchor;
+
+    #[test]
+    pub fn round_trip_test() {
+        let sf = (2.0, 0.5);
+        let mut trans = UiTransform::new(
+            "test".to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            sf.0 * 100.0,
+            sf.1 * 50.0,
+            0.5,
+            sf.0 * 300.0,
+            sf.1 * 40.0,
+        );
+        let scaled = ScreenScaled::new(&trans, None, sf);
+        assert_eq!((scaled.x, scaled.y), (100.0, 50.0));
+        assert_eq!((scaled.width, scaled.height), (300.0, 40.0));
+
+        scaled.apply((1.0, 1.0), &mut trans, None);
+        assert_eq!((trans.local_x, trans.local_y), (100.0, 50.0));
+        assert_eq!((trans.width, trans.height), (300.0, 40.0));
+    }
+
+    #[test]
+    pub fn square_test() {
+        let sf = (2.0, 0.5);
+        let mut trans = UiTransform::new(
+            "test".to_string(),
+            Anchor::Middle,
+            Anchor::Middle,
+            0.0,
+            0.0,
+            0.5,
+            sf.0 * 90.0,
+            sf.0 * 90.0,
+        );
+        let scaled = ScreenScaled::new_square(&trans, sf);
+
+        scaled.apply((3.0, 1.0), &mut trans, None);
+        assert_eq!(trans.width, trans.height);
+    }
+}
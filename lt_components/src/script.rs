@@ -0,0 +1,306 @@
+use crate::{events::GameEvent, win_related::GamePlayingMode};
+use amethyst::{
+    core::ecs::{Component, DenseVecStorage},
+    renderer::SpriteRender,
+};
+use serde::{Deserialize, Serialize};
+
+///How long the camera takes to pan to a tile, and the same again to pan back
+pub const PAN_TIME: f32 = 0.75;
+///How many times a second highlights pulse
+pub const HIGHLIGHT_PULSE_SPEED: f32 = 2.0;
+
+///What makes an entry in a level script happen - each entry only happens once
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScriptTrigger {
+    ///A number of seconds after the level starts - level messages get turned into these, see `ScriptEntry::from_messages`
+    Time(f32),
+    ///Once the player has made this many moves
+    Move(i32),
+    ///The first time two members of a tribe merge
+    FirstMerge,
+    ///When the player switches into a special mode
+    EnterMode(GamePlayingMode),
+    ///When a member of any tribe moves onto a tile, as (x, y)
+    ReachTile(usize, usize),
+    ///When the level is won - the level waits for the actions to finish before moving on
+    Won,
+}
+impl ScriptTrigger {
+    ///Gets the trigger that a GameEvent sets off, if there is one
+    ///
+    ///Time and Move triggers don't come from events, so they never get returned
+    pub fn from_event(event: &GameEvent) -> Option<Self> {
+        match event {
+            GameEvent::TribesMerged { .. } => Some(Self::FirstMerge),
+            GameEvent::ModeChanged { to, .. } => Some(Self::EnterMode(*to)),
+            GameEvent::TribeMoved { to, .. } if to.x >= 0 && to.y >= 0 => {
+                Some(Self::ReachTile(to.x as usize, to.y as usize))
+            }
+            GameEvent::LevelWon => Some(Self::Won),
+            _ => None,
+        }
+    }
+}
+
+///Something that a level script does
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScriptAction {
+    ///Shows a message, after any others that are waiting
+    Message(String),
+    ///Pans the camera to a tile, holds it there for *hold* seconds and then pans back - the player can't move while it does
+    PanCamera { x: usize, y: usize, hold: f32 },
+    ///Puts a pulsing frame over some tiles, as (x, y), for *time* seconds
+    Highlight {
+        tiles: Vec<(usize, usize)>,
+        time: f32,
+    },
+}
+
+///One entry in a level script - when the trigger happens, every action happens
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScriptEntry {
+    ///What makes the entry happen
+    pub trigger: ScriptTrigger,
+    ///What happens
+    pub actions: Vec<ScriptAction>,
+}
+impl ScriptEntry {
+    ///Turns the messages from a level into Time entries, so that they go through the script like everything else
+    ///
+    ///  - **messages** is each message, as (seconds after the message before it, message)
+    pub fn from_messages(messages: &[(f32, String)]) -> Vec<Self> {
+        let mut time = 0.0;
+        messages
+            .iter()
+            .map(|(delay, msg)| {
+                time += delay;
+                Self {
+                    trigger: ScriptTrigger::Time(time),
+                    actions: vec![ScriptAction::Message(msg.clone())],
+                }
+            })
+            .collect()
+    }
+}
+
+///Resource to hold the entries in the level script which haven't happened yet
+#[derive(Clone, Debug, Default)]
+pub struct LevelScript {
+    ///Entries which haven't happened yet
+    pub entries: Vec<ScriptEntry>,
+    ///How long the level has been going, in seconds
+    pub timer: f32,
+    ///How much longer the level needs to wait before moving on after it is won, in seconds
+    pub holding_for: f32,
+    ///The sprite to use for highlighting tiles
+    pub highlight_sprite: Option<SpriteRender>,
+}
+impl LevelScript {
+    ///Constructor for LevelScript
+    ///
+    ///  - **entries** is the script from the level
+    ///  - **highlight_sprite** is the sprite to use for highlighting tiles
+    pub fn new(entries: Vec<ScriptEntry>, highlight_sprite: SpriteRender) -> Self {
+        Self {
+            entries,
+            highlight_sprite: Some(highlight_sprite),
+            ..Default::default()
+        }
+    }
+
+    ///Removes every entry which has been triggered, and gets all of their actions
+    ///
+    ///  - **moves** is how many moves the player has made
+    ///  - **happened** is every trigger from this frame's events
+    pub fn take_triggered(&mut self, moves: i32, happened: &[ScriptTrigger]) -> Vec<ScriptAction> {
+        let timer = self.timer;
+        let (triggered, left): (Vec<ScriptEntry>, Vec<ScriptEntry>) =
+            std::mem::take(&mut self.entries)
+                .into_iter()
+                .partition(|entry| match entry.trigger {
+                    ScriptTrigger::Time(time) => timer >= time,
+                    ScriptTrigger::Move(n) => moves >= n,
+                    ref trigger => happened.contains(trigger),
+                });
+        self.entries = left;
+
+        triggered
+            .into_iter()
+            .flat_map(|entry| entry.actions)
+            .collect()
+    }
+
+    ///Whether or not the level should wait before moving on, because the outro is still going
+    pub fn is_holding(&self) -> bool {
+        self.holding_for > 0.0
+    }
+}
+
+///A camera pan from a level script
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CameraPan {
+    ///The tile to pan to, as (x, y)
+    pub target: (usize, usize),
+    ///How long to stay on the tile, in seconds
+    pub hold: f32,
+    ///How long the pan has been going, in seconds
+    pub so_far: f32,
+}
+impl CameraPan {
+    ///Constructor for a CameraPan which hasn't started yet
+    pub fn new(target: (usize, usize), hold: f32) -> Self {
+        Self {
+            target,
+            hold,
+            so_far: 0.0,
+        }
+    }
+
+    ///How long the whole pan takes, in seconds
+    pub fn total_time(&self) -> f32 {
+        PAN_TIME * 2.0 + self.hold
+    }
+
+    ///Whether or not the camera is back where it started
+    pub fn is_done(&self) -> bool {
+        self.so_far >= self.total_time()
+    }
+
+    ///How far the camera is towards the target, from 0.0 to 1.0
+    pub fn progress(&self) -> f32 {
+        let back_from = PAN_TIME + self.hold;
+        let linear = if self.so_far < PAN_TIME {
+            self.so_far / PAN_TIME
+        } else if self.so_far < back_from {
+            1.0
+        } else {
+            1.0 - (self.so_far - back_from) / PAN_TIME
+        };
+        linear.max(0.0).min(1.0)
+    }
+}
+
+///Component for the frames that highlight tiles, from a level script
+#[derive(Copy, Clone, Debug)]
+pub struct Highlight {
+    ///How much longer the highlight lasts, in seconds
+    pub left: f32,
+    ///How long the highlight has been going, in seconds
+    pub so_far: f32,
+}
+impl Highlight {
+    ///Constructor for a Highlight which lasts for *time* seconds
+    pub fn new(time: f32) -> Self {
+        Self {
+            left: time,
+            so_far: 0.0,
+        }
+    }
+
+    ///Moves the highlight along, and gets the alpha for its pulse - None if it has run out
+    pub fn tick(&mut self, delta: f32) -> Option<f32> {
+        self.left -= delta;
+        self.so_far += delta;
+        if self.left <= 0.0 {
+            return None;
+        }
+
+        let wave = (self.so_far * HIGHLIGHT_PULSE_SPEED * std::f32::consts::PI * 2.0).cos();
+        Some(0.6 + 0.4 * wave)
+    }
+}
+impl Component for Highlight {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile_transform::TileTransform;
+
+    fn message(entries: &[(ScriptTrigger, &str)]) -> Vec<ScriptEntry> {
+        entries
+            .iter()
+            .map(|(trigger, msg)| ScriptEntry {
+                trigger: trigger.clone(),
+                actions: vec![ScriptAction::Message(msg.to_string())],
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn triggers_test() {
+        let mut script = LevelScript {
+            entries: message(&[
+                (ScriptTrigger::Time(1.0), "time"),
+                (ScriptTrigger::Move(3), "move"),
+                (ScriptTrigger::FirstMerge, "merge"),
+                (ScriptTrigger::ReachTile(2, 5), "tile"),
+            ]),
+            ..Default::default()
+        };
+
+        assert!(script.take_triggered(0, &[]).is_empty());
+
+        script.timer = 1.5;
+        assert_eq!(
+            script.take_triggered(3, &[]),
+            vec![
+                ScriptAction::Message("time".into()),
+                ScriptAction::Message("move".into())
+            ]
+        );
+        assert!(
+            script.take_triggered(4, &[]).is_empty(),
+            "Only happens once"
+        );
+
+        let moved = GameEvent::TribeMoved {
+            tribe: 0,
+            from: TileTransform::new(2, 4),
+            to: TileTransform::new(2, 5),
+        };
+        let happened: Vec<ScriptTrigger> = ScriptTrigger::from_event(&moved).into_iter().collect();
+        assert_eq!(
+            script.take_triggered(4, &happened),
+            vec![ScriptAction::Message("tile".into())]
+        );
+        assert_eq!(script.entries.len(), 1);
+    }
+
+    #[test]
+    pub fn from_messages_test() {
+        let messages = vec![(0.5, "first".to_string()), (2.0, "second".to_string())];
+        assert_eq!(
+            ScriptEntry::from_messages(&messages),
+            message(&[
+                (ScriptTrigger::Time(0.5), "first"),
+                (ScriptTrigger::Time(2.5), "second"),
+            ])
+        );
+        assert!(ScriptEntry::from_messages(&[]).is_empty());
+    }
+
+    #[test]
+    pub fn pan_test() {
+        let mut pan = CameraPan::new((1, 1), 1.0);
+        assert_eq!(pan.progress(), 0.0);
+
+        pan.so_far = PAN_TIME + 0.5;
+        assert_eq!(pan.progress(), 1.0);
+        assert!(!pan.is_done());
+
+        pan.so_far = pan.total_time();
+        assert_eq!(pan.progress(), 0.0);
+        assert!(pan.is_done());
+    }
+
+    #[test]
+    pub fn highlight_test() {
+        let mut highlight = Highlight::new(1.0);
+        assert_eq!(highlight.tick(0.0), Some(1.0));
+        assert!(highlight.tick(0.5).is_some());
+        assert_eq!(highlight.tick(0.5), None);
+    }
+}
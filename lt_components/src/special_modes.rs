@@ -0,0 +1,559 @@
+use crate::{
+    occupancy::{tile_index, OccupancyGrid},
+    tile_transform::TileTransform,
+    win_related::GamePlayingMode,
+};
+use amethyst::input::VirtualKeyCode;
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+use rand::{Rng, RngCore};
+
+///One of the coloured copies of the level which get shown while a special mode is on
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FxLayer {
+    ///How far the copy is offset, in camera px
+    pub offset: (i32, i32),
+    ///The RGBA tint of the copy
+    pub colour: [f32; 4],
+}
+
+///The red and blue chromatic aberration used by most special modes
+pub const DEFAULT_FX: [FxLayer; 2] = [
+    FxLayer {
+        offset: (1, 1),
+        colour: [1.0, 0.0, 0.0, 0.5],
+    },
+    FxLayer {
+        offset: (-1, -1),
+        colour: [0.25, 0.0, 1.0, 0.5],
+    },
+];
+
+///A special mode, which changes how the tribes move in exchange for special moves
+///
+///Every hook apart from the mode, key and cost has a default which behaves like normal movement
+pub trait SpecialMode: Send + Sync {
+    ///The GamePlayingMode this is the behaviour for
+    fn mode(&self) -> GamePlayingMode;
+    ///The key which turns the mode on
+    fn key(&self) -> Option<VirtualKeyCode>;
+    ///How many special moves each move costs while the mode is on
+    fn cost(&self) -> i32;
+
+    ///Whether or not moves add to the score
+    fn adds_to_score(&self) -> bool {
+        true
+    }
+
+    ///Transforms the player's input before any tribe member moves
+    ///
+    ///  - **input** is the direction from the keys, as a single step
+    fn transform_input(&self, input: TileTransform) -> TileTransform {
+        input
+    }
+
+    ///Gets where a tribe member tries to move to
+    ///
+    ///  - **from** is where the tribe member is
+    ///  - **input** is the transformed input
+    ///  - **tribe** is the id of the tribe
+    ///  - **rng** is the random number generator for modes which need one
+    fn propose_move(
+        &self,
+        from: TileTransform,
+        input: TileTransform,
+        _tribe: usize,
+        _rng: &mut dyn RngCore,
+    ) -> TileTransform {
+        from + input
+    }
+
+    ///Gets how long the movement animation takes
+    ///
+    ///  - **base_len** is the normal length of the animation
+    fn anim_len(&self, base_len: f32) -> f32 {
+        base_len
+    }
+
+    ///Whether or not the movement animation eases out, rather than being linear
+    fn smooth_anim(&self) -> bool {
+        false
+    }
+
+    ///Checks whether a tribe member can move onto a tile
+    ///
+    ///  - **proposed_tile** is the tile to check
+    ///  - **grid** is the grid of what is on each tile
+    fn tile_works(&self, proposed_tile: TileTransform, grid: &OccupancyGrid) -> bool {
+        !grid.is_blocked(&proposed_tile, false)
+    }
+
+    ///Whether or not tribe members push blocks
+    fn pushes_blocks(&self) -> bool {
+        true
+    }
+
+    ///Whether or not a move swaps tribes around with `swap_pairs`, rather than moving tribe members
+    fn swaps_tribes(&self) -> bool {
+        false
+    }
+
+    ///Gets the coloured copies of the level to show while the mode is on
+    fn fx_layers(&self) -> Vec<FxLayer> {
+        DEFAULT_FX.to_vec()
+    }
+}
+
+///One move - all moves
+pub struct BoringMode;
+impl SpecialMode for BoringMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Boring
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::B)
+    }
+    fn cost(&self) -> i32 {
+        0
+    }
+    fn fx_layers(&self) -> Vec<FxLayer> {
+        vec![]
+    }
+}
+
+///Collisions are ignored
+pub struct NudgerMode;
+impl SpecialMode for NudgerMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Nudger
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::N)
+    }
+    fn cost(&self) -> i32 {
+        2
+    }
+    fn tile_works(&self, proposed_tile: TileTransform, _grid: &OccupancyGrid) -> bool {
+        tile_index(&proposed_tile).is_some()
+    }
+    fn pushes_blocks(&self) -> bool {
+        false
+    }
+}
+
+///All movements go in a random direction, but are free
+pub struct TradeOffMode;
+impl SpecialMode for TradeOffMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::TradeOff
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::T)
+    }
+    fn cost(&self) -> i32 {
+        1
+    }
+    fn adds_to_score(&self) -> bool {
+        false
+    }
+    fn propose_move(
+        &self,
+        from: TileTransform,
+        _input: TileTransform,
+        _tribe: usize,
+        rng: &mut dyn RngCore,
+    ) -> TileTransform {
+        from + TileTransform::new(rng.gen_range(-1..=1), rng.gen_range(-1..=1))
+    }
+}
+
+///All movements go to a random place
+pub struct CrazyMode;
+impl SpecialMode for CrazyMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Crazy
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::C)
+    }
+    fn cost(&self) -> i32 {
+        3
+    }
+    fn propose_move(
+        &self,
+        _from: TileTransform,
+        _input: TileTransform,
+        _tribe: usize,
+        rng: &mut dyn RngCore,
+    ) -> TileTransform {
+        TileTransform::new(rng.gen_range(0..WIDTH), rng.gen_range(0..HEIGHT))
+    }
+    fn anim_len(&self, base_len: f32) -> f32 {
+        base_len * 3.0
+    }
+    fn smooth_anim(&self) -> bool {
+        true
+    }
+}
+
+///Everything is a collider
+pub struct AllTheCollidersMode;
+impl SpecialMode for AllTheCollidersMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::AllTheColliders
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::K)
+    }
+    fn cost(&self) -> i32 {
+        10
+    }
+    fn tile_works(&self, proposed_tile: TileTransform, grid: &OccupancyGrid) -> bool {
+        !grid.is_blocked(&proposed_tile, true)
+    }
+}
+
+///Sorry, you tried to move once? make that three!
+pub struct FrenzyMode;
+impl SpecialMode for FrenzyMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Frenzy
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::F)
+    }
+    fn cost(&self) -> i32 {
+        5
+    }
+    fn transform_input(&self, input: TileTransform) -> TileTransform {
+        input * 3
+    }
+}
+
+///Every other tribe moves the opposite way - each tribe moves in the opposite direction to the tribe with the ID before it
+pub struct MirrorMode;
+impl SpecialMode for MirrorMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Mirror
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::M)
+    }
+    fn cost(&self) -> i32 {
+        2
+    }
+    fn propose_move(
+        &self,
+        from: TileTransform,
+        input: TileTransform,
+        tribe: usize,
+        _rng: &mut dyn RngCore,
+    ) -> TileTransform {
+        if tribe % 2 == 0 {
+            from + input
+        } else {
+            from - input
+        }
+    }
+    fn fx_layers(&self) -> Vec<FxLayer> {
+        vec![
+            FxLayer {
+                offset: (2, 0),
+                colour: [0.0, 1.0, 1.0, 0.5],
+            },
+            FxLayer {
+                offset: (-2, 0),
+                colour: [1.0, 0.5, 0.0, 0.5],
+            },
+        ]
+    }
+}
+
+///Any move swaps the selected tribe with the next tribe along, rather than moving anyone
+pub struct SwapMode;
+impl SpecialMode for SwapMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Swap
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::X)
+    }
+    fn cost(&self) -> i32 {
+        4
+    }
+    fn swaps_tribes(&self) -> bool {
+        true
+    }
+    fn fx_layers(&self) -> Vec<FxLayer> {
+        vec![
+            FxLayer {
+                offset: (0, 2),
+                colour: [0.0, 1.0, 0.25, 0.5],
+            },
+            FxLayer {
+                offset: (0, -2),
+                colour: [1.0, 0.0, 1.0, 0.5],
+            },
+        ]
+    }
+}
+
+///Tribe members can only move onto triggers, like doors, switches and other tribe members - so only tribes next to them move
+pub struct PhaseMode;
+impl SpecialMode for PhaseMode {
+    fn mode(&self) -> GamePlayingMode {
+        GamePlayingMode::Phase
+    }
+    fn key(&self) -> Option<VirtualKeyCode> {
+        Some(VirtualKeyCode::P)
+    }
+    fn cost(&self) -> i32 {
+        1
+    }
+    fn tile_works(&self, proposed_tile: TileTransform, grid: &OccupancyGrid) -> bool {
+        !grid.is_blocked(&proposed_tile, false) && grid.has_trigger(&proposed_tile)
+    }
+    fn pushes_blocks(&self) -> bool {
+        false
+    }
+    fn fx_layers(&self) -> Vec<FxLayer> {
+        vec![
+            FxLayer {
+                offset: (1, -1),
+                colour: [1.0, 1.0, 1.0, 0.3],
+            },
+            FxLayer {
+                offset: (-1, 1),
+                colour: [1.0, 1.0, 1.0, 0.3],
+            },
+        ]
+    }
+}
+
+///Gets which tribe members swap places in a swapping mode
+///
+///The selected tribe swaps with the next tribe ID along, wrapping around. Members are paired up in order, so any spare members of the bigger tribe stay put
+///
+///Returns pairs of indices into **members**
+///
+///  - **members** is the tribe id and position of every tribe member
+///  - **selected** is the selected tribe - if it isn't in the level, the lowest tribe ID gets used
+pub fn swap_pairs(members: &[(usize, TileTransform)], selected: usize) -> Vec<(usize, usize)> {
+    let mut tribes: Vec<usize> = members.iter().map(|(tribe, _)| *tribe).collect();
+    tribes.sort_unstable();
+    tribes.dedup();
+    if tribes.len() < 2 {
+        return vec![];
+    }
+
+    let first = if tribes.contains(&selected) {
+        selected
+    } else {
+        tribes[0]
+    };
+    let partner = *tribes.iter().find(|id| **id > first).unwrap_or(&tribes[0]);
+
+    let indices = |tribe: usize| {
+        members
+            .iter()
+            .enumerate()
+            .filter(move |(_, (t, _))| *t == tribe)
+            .map(|(i, _)| i)
+    };
+    indices(first).zip(indices(partner)).collect()
+}
+
+///Holds the behaviour for every GamePlayingMode - new modes just need to be registered here
+pub struct ModeRegistry {
+    ///Every registered mode, in the order they were registered
+    modes: Vec<Box<dyn SpecialMode>>,
+}
+impl Default for ModeRegistry {
+    fn default() -> Self {
+        let mut registry = Self { modes: vec![] };
+        registry.register(Box::new(BoringMode));
+        registry.register(Box::new(NudgerMode));
+        registry.register(Box::new(TradeOffMode));
+        registry.register(Box::new(CrazyMode));
+        registry.register(Box::new(AllTheCollidersMode));
+        registry.register(Box::new(FrenzyMode));
+        registry.register(Box::new(MirrorMode));
+        registry.register(Box::new(SwapMode));
+        registry.register(Box::new(PhaseMode));
+        registry
+    }
+}
+impl ModeRegistry {
+    ///Adds a mode to the registry, replacing any other behaviour for the same GamePlayingMode
+    pub fn register(&mut self, mode: Box<dyn SpecialMode>) {
+        self.modes.retain(|m| m.mode() != mode.mode());
+        self.modes.push(mode);
+    }
+
+    ///Gets the behaviour for a mode - anything not registered behaves like Boring
+    pub fn get(&self, mode: GamePlayingMode) -> &dyn SpecialMode {
+        match self.modes.iter().find(|m| m.mode() == mode) {
+            Some(m) => m.as_ref(),
+            None => &BoringMode,
+        }
+    }
+
+    ///Gets the mode which a key turns on
+    pub fn from_key(&self, key: VirtualKeyCode) -> Option<GamePlayingMode> {
+        self.modes
+            .iter()
+            .find(|m| m.key() == Some(key))
+            .map(|m| m.mode())
+    }
+
+    ///Gets an iterator over every registered mode
+    pub fn iter(&self) -> impl Iterator<Item = &dyn SpecialMode> {
+        self.modes.iter().map(|m| m.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lonely_tribes_tags::trigger_type::TriggerType;
+
+    #[test]
+    pub fn registry_test() {
+        let mut registry = ModeRegistry::default();
+        assert_eq!(registry.iter().count(), 9);
+        assert_eq!(
+            registry.from_key(VirtualKeyCode::F),
+            Some(GamePlayingMode::Frenzy)
+        );
+        assert_eq!(registry.from_key(VirtualKeyCode::Q), None);
+        assert_eq!(registry.get(GamePlayingMode::AllTheColliders).cost(), 10);
+
+        struct CheapFrenzy;
+        impl SpecialMode for CheapFrenzy {
+            fn mode(&self) -> GamePlayingMode {
+                GamePlayingMode::Frenzy
+            }
+            fn key(&self) -> Option<VirtualKeyCode> {
+                Some(VirtualKeyCode::Q)
+            }
+            fn cost(&self) -> i32 {
+                1
+            }
+        }
+        registry.register(Box::new(CheapFrenzy));
+        assert_eq!(registry.iter().count(), 9, "Replaces the old Frenzy");
+        assert_eq!(registry.get(GamePlayingMode::Frenzy).cost(), 1);
+        assert_eq!(registry.from_key(VirtualKeyCode::F), None);
+    }
+
+    #[test]
+    pub fn movement_hooks_test() {
+        let registry = ModeRegistry::default();
+        let mut rng = rand::thread_rng();
+        let from = TileTransform::new(5, 5);
+        let input = TileTransform::new(1, 0);
+
+        let frenzy = registry.get(GamePlayingMode::Frenzy);
+        let to = frenzy.propose_move(from, frenzy.transform_input(input), 0, &mut rng);
+        assert_eq!(to, TileTransform::new(8, 5));
+
+        let trade_off = registry.get(GamePlayingMode::TradeOff);
+        let to = trade_off.propose_move(from, input, 0, &mut rng);
+        assert!((to.x - from.x).abs() <= 1 && (to.y - from.y).abs() <= 1);
+        assert!(!trade_off.adds_to_score());
+
+        let crazy = registry.get(GamePlayingMode::Crazy);
+        assert!(tile_index(&crazy.propose_move(from, input, 0, &mut rng)).is_some());
+        assert!(crazy.smooth_anim());
+        assert_eq!(crazy.anim_len(1.0), 3.0);
+    }
+
+    #[test]
+    pub fn collision_hooks_test() {
+        let registry = ModeRegistry::default();
+        let mut grid = OccupancyGrid::empty();
+        let wall = TileTransform::new(1, 1);
+        let member = TileTransform::new(2, 2);
+        grid.add_collider(&wall);
+        grid.add_player(&member, 0);
+
+        let boring = registry.get(GamePlayingMode::Boring);
+        assert!(!boring.tile_works(wall, &grid));
+        assert!(boring.tile_works(member, &grid));
+        assert!(boring.fx_layers().is_empty());
+
+        let nudger = registry.get(GamePlayingMode::Nudger);
+        assert!(nudger.tile_works(wall, &grid));
+        assert!(!nudger.tile_works(TileTransform::new(-1, 0), &grid));
+        assert!(!nudger.pushes_blocks());
+
+        let colliders = registry.get(GamePlayingMode::AllTheColliders);
+        assert!(!colliders.tile_works(member, &grid));
+        assert_eq!(colliders.fx_layers(), DEFAULT_FX.to_vec());
+    }
+
+    #[test]
+    pub fn mirror_test() {
+        let registry = ModeRegistry::default();
+        let mirror = registry.get(GamePlayingMode::Mirror);
+        let mut rng = rand::thread_rng();
+        let from = TileTransform::new(5, 5);
+        let input = TileTransform::new(0, -1);
+
+        let got: Vec<TileTransform> = (0..4)
+            .map(|tribe| mirror.propose_move(from, input, tribe, &mut rng))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                TileTransform::new(5, 4),
+                TileTransform::new(5, 6),
+                TileTransform::new(5, 4),
+                TileTransform::new(5, 6)
+            ]
+        );
+        assert_eq!(mirror.cost(), 2);
+        assert_eq!(mirror.fx_layers().len(), 2);
+    }
+
+    #[test]
+    pub fn swap_test() {
+        let registry = ModeRegistry::default();
+        let swap = registry.get(GamePlayingMode::Swap);
+        assert!(swap.swaps_tribes());
+        assert_eq!(swap.cost(), 4);
+        assert!(!swap.fx_layers().is_empty());
+
+        let members = [
+            (2, TileTransform::new(0, 0)),
+            (0, TileTransform::new(1, 0)),
+            (2, TileTransform::new(2, 0)),
+            (3, TileTransform::new(3, 0)),
+            (0, TileTransform::new(4, 0)),
+        ];
+        assert_eq!(swap_pairs(&members, 0), vec![(1, 0), (4, 2)]);
+        assert_eq!(swap_pairs(&members, 3), vec![(3, 1)], "Wraps around");
+        assert_eq!(swap_pairs(&members, 1), vec![(1, 0), (4, 2)]);
+        assert!(swap_pairs(&members[..1], 2).is_empty());
+    }
+
+    #[test]
+    pub fn phase_test() {
+        let registry = ModeRegistry::default();
+        let phase = registry.get(GamePlayingMode::Phase);
+        let mut grid = OccupancyGrid::empty();
+        let door = TileTransform::new(1, 1);
+        let member = TileTransform::new(2, 2);
+        let wall = TileTransform::new(3, 3);
+        grid.add_trigger(&door, TriggerType::Door);
+        grid.add_player(&member, 0);
+        grid.add_collider(&wall);
+
+        assert!(phase.tile_works(door, &grid));
+        assert!(phase.tile_works(member, &grid));
+        assert!(!phase.tile_works(wall, &grid));
+        assert!(!phase.tile_works(TileTransform::new(4, 4), &grid));
+        assert!(!phase.pushes_blocks());
+        assert_eq!(phase.cost(), 1);
+        assert_eq!(phase.fx_layers().len(), 2);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::colliders::Collider;
+use amethyst::core::ecs::{Component, DenseVecStorage};
+use lonely_tribes_tags::trigger_type::TriggerType;
+use std::collections::HashSet;
+
+///Component for a pressure plate, switch or gate, which are linked together by their link ids
+///
+///Pressure plates and switches are active when pressed or switched on, and gates are active when open
+#[derive(Copy, Clone, Debug)]
+pub struct Linked {
+    ///What kind of linked tile this is, along with its link id
+    pub trigger: TriggerType,
+    ///Whether or not it is currently active
+    pub active: bool,
+    ///The sprite indices for when it is inactive, and when it is active
+    pub sprites: [usize; 2],
+}
+impl Linked {
+    ///Constructor for an inactive linked tile
+    ///
+    ///  - **trigger** is the kind of tile and its link id - should be a pressure plate, switch or gate
+    ///  - **sprites** is the sprite indices for when it is inactive, and when it is active
+    pub fn new(trigger: TriggerType, sprites: [usize; 2]) -> Self {
+        Self {
+            trigger,
+            active: false,
+            sprites,
+        }
+    }
+
+    ///Gets the link id, or 0 if the trigger doesn't have one
+    pub fn link(&self) -> usize {
+        self.trigger.link().unwrap_or_default()
+    }
+
+    ///Checks whether or not this is a gate
+    pub fn is_gate(&self) -> bool {
+        matches!(self.trigger, TriggerType::Gate(_))
+    }
+
+    ///Gets the sprite index for the current state
+    pub fn sprite(&self) -> usize {
+        self.sprites[self.active as usize]
+    }
+
+    ///Gets the collider for the current state - closed gates are normal colliders, and everything else is a trigger
+    pub fn collider(&self) -> Collider {
+        if self.is_gate() && !self.active {
+            Collider::default()
+        } else {
+            Collider::new(self.trigger)
+        }
+    }
+
+    ///Updates a pressure plate or switch, and returns whether or not it changed
+    ///
+    ///  - **weighed_down** is whether or not something is on the tile
+    ///  - **stepped_on** is whether or not a tribe member just moved onto the tile
+    pub fn update_activator(&mut self, weighed_down: bool, stepped_on: bool) -> bool {
+        let nu = match self.trigger {
+            TriggerType::PressurePlate(_) => weighed_down,
+            TriggerType::Switch(_) => self.active != stepped_on,
+            _ => self.active,
+        };
+
+        let changed = nu != self.active;
+        self.active = nu;
+        changed
+    }
+
+    ///Updates a gate, and returns whether or not it changed
+    ///
+    ///Gates can't close while something is in the way
+    ///
+    ///  - **active_links** is every link id with an active pressure plate or switch
+    ///  - **occupied** is whether or not something is on the gate
+    pub fn update_gate(&mut self, active_links: &HashSet<usize>, occupied: bool) -> bool {
+        if !self.is_gate() {
+            return false;
+        }
+
+        let nu = active_links.contains(&self.link()) || (self.active && occupied);
+        let changed = nu != self.active;
+        self.active = nu;
+        changed
+    }
+}
+impl Component for Linked {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn activator_test() {
+        let mut plate = Linked::new(TriggerType::PressurePlate(1), [0, 1]);
+        assert!(plate.update_activator(true, true));
+        assert_eq!(plate.sprite(), 1);
+        assert!(!plate.update_activator(true, false));
+        assert!(plate.update_activator(false, false));
+
+        let mut switch = Linked::new(TriggerType::Switch(1), [0, 1]);
+        assert!(switch.update_activator(true, true));
+        assert!(!switch.update_activator(false, false), "Switches stay on");
+        assert!(switch.update_activator(true, true));
+        assert!(!switch.active);
+    }
+
+    #[test]
+    pub fn gate_test() {
+        let mut gate = Linked::new(TriggerType::Gate(2), [0, 1]);
+        let mut active = HashSet::new();
+        assert!(gate.collider().trigger.is_none());
+
+        assert!(!gate.update_gate(&active, false));
+        active.insert(2);
+        assert!(gate.update_gate(&active, false));
+        assert_eq!(gate.collider().trigger, Some(TriggerType::Gate(2)));
+
+        active.clear();
+        assert!(
+            !gate.update_gate(&active, true),
+            "Gates stay open while blocked"
+        );
+        assert!(gate.update_gate(&active, false));
+        assert!(!gate.active);
+    }
+}
@@ -1,4 +1,4 @@
-use amethyst::core::ecs::{Component, DefaultVecStorage};
+use amethyst::core::ecs::{Component, DefaultVecStorage, FlaggedStorage};
 use std::{
     cmp::Ordering,
     fmt::{Display, Formatter},
@@ -21,7 +21,8 @@ pub struct TileTransform {
     pub y_offset: i32,
 }
 impl Component for TileTransform {
-    type Storage = DefaultVecStorage<Self>;
+    ///Flagged, so the fog of war only needs to update entities which have moved
+    type Storage = FlaggedStorage<Self, DefaultVecStorage<Self>>;
 }
 impl Default for TileTransform {
     fn default() -> Self {
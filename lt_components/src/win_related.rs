@@ -1,4 +1,10 @@
-use crate::tile_transform::TileTransform;
+use crate::{
+    events::{GameEvent, GameEventChannel},
+    special_modes::ModeRegistry,
+    tile_transform::TileTransform,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 ///Enumeration for the current state of the game
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -19,7 +25,9 @@ impl Default for GameStateEnum {
 }
 
 ///The mode for gameplay - not the game state or the win state, but the mode of gameplay
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+///
+///The behaviour for each mode lives in its SpecialMode in the ModeRegistry
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum GamePlayingMode {
     ///One move - all moves
     Boring,
@@ -33,26 +41,26 @@ pub enum GamePlayingMode {
     AllTheColliders,
     ///Sorry, you tried to move once? make that three!
     Frenzy,
+    ///Every other tribe moves the opposite way
+    Mirror,
+    ///The selected tribe swaps places with the next tribe along
+    Swap,
+    ///Tribe members can only move onto triggers
+    Phase,
 }
 impl Default for GamePlayingMode {
     fn default() -> Self {
         Self::Boring
     }
 }
-impl GamePlayingMode {
-    pub fn get_no_moves(&self) -> i32 {
-        match self {
-            Self::Boring => 0,
-            Self::Nudger => 2,
-            Self::TradeOff => 1,
-            Self::Crazy => 3,
-            Self::AllTheColliders => 10,
-            Self::Frenzy => 5,
-        }
-    }
-    pub fn adds_to_score(&self) -> bool {
-        !(self == &Self::TradeOff)
-    }
+
+///A separate pool of special moves for one mode, rather than the shared pool
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ModeBudget {
+    ///How many moves the pool started with
+    pub total: i32,
+    ///How many moves are left in the pool
+    pub left: i32,
 }
 
 ///The struct that holds the current GamePlayingMode, as well as the number of moves left
@@ -60,6 +68,12 @@ pub struct GameModeManager {
     pub total_moves: i32,
     pub moves_left: i32,
     pub current_mode: GamePlayingMode,
+    ///The behaviour for every mode
+    pub modes: ModeRegistry,
+    ///Which modes can be used in this level - if None, every mode can be
+    pub allowed: Option<Vec<GamePlayingMode>>,
+    ///Modes which spend from their own pool of moves rather than the shared one
+    pub budgets: HashMap<GamePlayingMode, ModeBudget>,
 }
 impl Default for GameModeManager {
     fn default() -> Self {
@@ -67,6 +81,9 @@ impl Default for GameModeManager {
             total_moves: 10,
             moves_left: 10,
             current_mode: GamePlayingMode::default(),
+            modes: ModeRegistry::default(),
+            allowed: None,
+            budgets: HashMap::new(),
         }
     }
 }
@@ -77,17 +94,123 @@ impl GameModeManager {
             total_moves: moves,
             moves_left: moves,
             current_mode: GamePlayingMode::Boring,
+            modes: ModeRegistry::default(),
+            allowed: None,
+            budgets: HashMap::new(),
         }
     }
 
-    pub fn do_move(&mut self) {
-        self.moves_left -= self.current_mode.get_no_moves();
-        self.get_and_update_mode();
+    ///Constructor for a level which limits its special modes
+    ///
+    ///  - **moves** is the number of moves in the shared pool
+    ///  - **allowed** is which modes can be used - if None, every mode can be
+    ///  - **budgets** is the modes which have their own pool of moves, along with how many moves are in it
+    pub fn with_rules(
+        moves: i32,
+        allowed: Option<Vec<GamePlayingMode>>,
+        budgets: &[(GamePlayingMode, i32)],
+    ) -> Self {
+        Self {
+            allowed,
+            budgets: budgets
+                .iter()
+                .map(|(mode, total)| {
+                    (
+                        *mode,
+                        ModeBudget {
+                            total: *total,
+                            left: *total,
+                        },
+                    )
+                })
+                .collect(),
+            ..Self::new(moves)
+        }
     }
 
-    pub fn set_mode(&mut self, nu_mode: GamePlayingMode) -> bool {
-        if self.moves_left > 0 {
-            self.current_mode = nu_mode;
+    ///Checks whether or not a mode can be used in this level - Boring always can be
+    pub fn is_allowed(&self, mode: GamePlayingMode) -> bool {
+        mode == GamePlayingMode::Boring
+            || self
+                .allowed
+                .as_ref()
+                .map_or(true, |allowed| allowed.contains(&mode))
+    }
+
+    ///Gets how many moves are left for a mode, from its own pool if it has one or the shared pool if not
+    pub fn moves_left_for(&self, mode: GamePlayingMode) -> i32 {
+        self.budgets
+            .get(&mode)
+            .map_or(self.moves_left, |budget| budget.left)
+    }
+
+    ///Gets the fraction of special moves left for the current mode, from 0.0 to 1.0
+    ///
+    ///Uses the current mode's own pool if it has one, or the shared pool if not
+    pub fn fraction_left(&self) -> f32 {
+        let (left, total) = self
+            .budgets
+            .get(&self.current_mode)
+            .map_or((self.moves_left, self.total_moves), |budget| {
+                (budget.left, budget.total)
+            });
+        if total <= 0 {
+            return 0.0;
+        }
+
+        (left as f32 / total as f32).max(0.0).min(1.0)
+    }
+
+    ///Whether or not the level gave the player any special moves to use, counting the moves the pools started with
+    pub fn has_specials(&self) -> bool {
+        self.modes
+            .iter()
+            .map(|m| m.mode())
+            .filter(|mode| mode != &GamePlayingMode::Boring && self.is_allowed(*mode))
+            .any(|mode| {
+                self.budgets
+                    .get(&mode)
+                    .map_or(self.total_moves, |budget| budget.total)
+                    > 0
+            })
+    }
+
+    ///Gets every mode which can be used right now apart from Boring, along with its cost and how many moves are left for it
+    ///
+    ///Modes with no moves left for them aren't included
+    pub fn available_modes(&self) -> Vec<(GamePlayingMode, i32, i32)> {
+        self.modes
+            .iter()
+            .map(|m| m.mode())
+            .filter(|mode| {
+                mode != &GamePlayingMode::Boring
+                    && self.is_allowed(*mode)
+                    && self.moves_left_for(*mode) > 0
+            })
+            .map(|mode| (mode, self.modes.get(mode).cost(), self.moves_left_for(mode)))
+            .collect()
+    }
+
+    ///Uses up the moves for the current mode, and sends a ModeChanged event if that runs out the mode
+    pub fn do_move(&mut self, events: &mut GameEventChannel) {
+        let cost = self.modes.get(self.current_mode).cost();
+        match self.budgets.get_mut(&self.current_mode) {
+            Some(budget) => budget.left -= cost,
+            None => self.moves_left -= cost,
+        }
+        self.get_and_update_mode(events);
+    }
+
+    ///Sets the mode if it is allowed and there are moves left for it, and sends a ModeChanged event if it changed
+    ///
+    ///Going back to Boring always works
+    ///
+    ///Returns whether or not the mode was set
+    pub fn set_mode(&mut self, nu_mode: GamePlayingMode, events: &mut GameEventChannel) -> bool {
+        if nu_mode == GamePlayingMode::Boring
+            || (self.is_allowed(nu_mode) && self.moves_left_for(nu_mode) > 0)
+        {
+            self.change_mode(nu_mode, events);
             log::info!("Mode is now {:?}", nu_mode);
             true
         } else {
@@ -95,12 +218,23 @@ impl GameModeManager {
         }
     }
 
-    pub fn get_and_update_mode(&mut self) -> GamePlayingMode {
-        if self.moves_left <= 0 {
-            self.current_mode = GamePlayingMode::Boring;
+    ///Sets the mode back to Boring if there are no moves left, and then gets the current mode
+    pub fn get_and_update_mode(&mut self, events: &mut GameEventChannel) -> GamePlayingMode {
+        if self.moves_left_for(self.current_mode) <= 0 {
+            self.change_mode(GamePlayingMode::Boring, events);
         }
         self.current_mode
     }
+
+    fn change_mode(&mut self, nu_mode: GamePlayingMode, events: &mut GameEventChannel) {
+        if self.current_mode != nu_mode {
+            events.single_write(GameEvent::ModeChanged {
+                from: self.current_mode,
+                to: nu_mode,
+            });
+            self.current_mode = nu_mode;
+        }
+    }
 }
 
 //for the thing in the world, have a struct with a tuple of 'hacker' moves left, and total 'hacker' moves, and the current mode
@@ -156,3 +290,107 @@ impl GameState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn mode_changed_events_test() {
+        let mut events = GameEventChannel::new();
+        let mut reader = events.register_reader();
+        let mut gm = GameModeManager::new(3);
+
+        assert!(gm.set_mode(GamePlayingMode::Crazy, &mut events));
+        gm.do_move(&mut events);
+
+        let got: Vec<GameEvent> = events.read(&mut reader).cloned().collect();
+        assert_eq!(
+            got,
+            vec![
+                GameEvent::ModeChanged {
+                    from: GamePlayingMode::Boring,
+                    to: GamePlayingMode::Crazy
+                },
+                GameEvent::ModeChanged {
+                    from: GamePlayingMode::Crazy,
+                    to: GamePlayingMode::Boring
+                },
+            ]
+        );
+        assert!(!gm.set_mode(GamePlayingMode::Nudger, &mut events));
+    }
+
+    #[test]
+    pub fn allowed_modes_test() {
+        let mut events = GameEventChannel::new();
+        let mut gm = GameModeManager::with_rules(10, Some(vec![GamePlayingMode::Nudger]), &[]);
+
+        assert!(!gm.set_mode(GamePlayingMode::Frenzy, &mut events));
+        assert_eq!(gm.current_mode, GamePlayingMode::Boring);
+        assert!(gm.set_mode(GamePlayingMode::Nudger, &mut events));
+        assert!(gm.set_mode(GamePlayingMode::Boring, &mut events));
+        assert_eq!(gm.available_modes(), vec![(GamePlayingMode::Nudger, 2, 10)]);
+
+        let none_left = GameModeManager::with_rules(0, None, &[(GamePlayingMode::Nudger, 4)]);
+        assert_eq!(
+            none_left.available_modes(),
+            vec![(GamePlayingMode::Nudger, 2, 4)]
+        );
+    }
+
+    #[test]
+    pub fn budgets_test() {
+        let mut events = GameEventChannel::new();
+        let mut gm = GameModeManager::with_rules(0, None, &[(GamePlayingMode::Nudger, 4)]);
+
+        assert!(!gm.set_mode(GamePlayingMode::Crazy, &mut events));
+        assert!(gm.set_mode(GamePlayingMode::Nudger, &mut events));
+        gm.do_move(&mut events);
+        assert_eq!(gm.moves_left_for(GamePlayingMode::Nudger), 2);
+        assert_eq!(gm.moves_left, 0);
+        assert_eq!(gm.current_mode, GamePlayingMode::Nudger);
+
+        gm.do_move(&mut events);
+        assert_eq!(gm.current_mode, GamePlayingMode::Boring, "Runs out");
+        assert!(!gm.set_mode(GamePlayingMode::Nudger, &mut events));
+    }
+
+    #[test]
+    pub fn has_specials_test() {
+        let mut events = GameEventChannel::new();
+        assert!(!GameModeManager::new(0).has_specials());
+        assert!(
+            GameModeManager::with_rules(0, None, &[(GamePlayingMode::Nudger, 2)]).has_specials()
+        );
+        assert!(!GameModeManager::with_rules(5, Some(vec![]), &[]).has_specials());
+
+        let mut gm = GameModeManager::new(2);
+        gm.set_mode(GamePlayingMode::Nudger, &mut events);
+        gm.do_move(&mut events);
+        assert!(gm.has_specials(), "Still counts once they are used up");
+    }
+
+    #[test]
+    pub fn fraction_left_test() {
+        let mut events = GameEventChannel::new();
+        let mut gm = GameModeManager::with_rules(8, None, &[(GamePlayingMode::Crazy, 6)]);
+        assert_eq!(gm.fraction_left(), 1.0);
+
+        gm.set_mode(GamePlayingMode::Nudger, &mut events);
+        gm.do_move(&mut events);
+        assert_eq!(gm.fraction_left(), 0.75);
+
+        gm.set_mode(GamePlayingMode::Crazy, &mut events);
+        gm.do_move(&mut events);
+        assert_eq!(gm.fraction_left(), 0.5, "Uses the mode's own pool");
+
+        gm.do_move(&mut events);
+        assert_eq!(
+            gm.fraction_left(),
+            0.75,
+            "Back to the shared pool after running out"
+        );
+        assert_eq!(GameModeManager::new(0).fraction_left(), 0.0);
+    }
+}
@@ -0,0 +1,103 @@
+use lonely_tribes_components::{
+    fog_memory::FogMemory, occupancy::tile_index, tile_transform::TileTransform,
+};
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+
+///Lighting factor for tiles which have been seen before, but aren't currently lit
+pub const REMEMBERED_FACTOR: f32 = 0.2;
+
+///Resource to keep track of which tiles have been seen in the current level
+#[derive(Clone, Debug)]
+pub struct ExploredTiles {
+    ///The kind of memory for this level
+    memory: FogMemory,
+    ///Whether or not each tile has been seen, indexed by `x + y * WIDTH`
+    seen: Vec<bool>,
+}
+impl Default for ExploredTiles {
+    fn default() -> Self {
+        Self::new(FogMemory::default())
+    }
+}
+
+impl ExploredTiles {
+    ///Constructor for ExploredTiles, with nothing seen yet
+    ///
+    ///  - **memory** is how much the fog of war remembers
+    pub fn new(memory: FogMemory) -> Self {
+        Self {
+            memory,
+            seen: vec![memory == FogMemory::FullReveal; (WIDTH * HEIGHT) as usize],
+        }
+    }
+
+    ///Gets whether or not a tile has been seen
+    pub fn is_seen(&self, t: &TileTransform) -> bool {
+        tile_index(t).map_or(false, |i| self.seen[i])
+    }
+
+    ///Gets the lighting factor for a tile, marking it as seen if it is currently lit
+    ///
+    ///  - **t** is the tile
+    ///  - **light_factor** is how lit the tile currently is
+    pub fn factor(&mut self, t: &TileTransform, light_factor: f32) -> f32 {
+        if self.memory == FogMemory::None {
+            return light_factor;
+        }
+
+        let i = match tile_index(t) {
+            Some(i) => i,
+            None => return light_factor,
+        };
+        if light_factor > 0.0 {
+            self.seen[i] = true;
+        }
+
+        if self.seen[i] {
+            light_factor.max(REMEMBERED_FACTOR)
+        } else {
+            light_factor
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn no_memory_test() {
+        let mut explored = ExploredTiles::new(FogMemory::None);
+        let t = TileTransform::new(3, 4);
+
+        assert_eq!(explored.factor(&t, 0.8), 0.8);
+        assert_eq!(explored.factor(&t, 0.0), 0.0);
+        assert!(!explored.is_seen(&t));
+    }
+
+    #[test]
+    pub fn layout_memory_test() {
+        let mut explored = ExploredTiles::new(FogMemory::Layout);
+        let seen = TileTransform::new(3, 4);
+        let unseen = TileTransform::new(5, 6);
+
+        assert_eq!(explored.factor(&seen, 0.8), 0.8);
+        assert_eq!(explored.factor(&seen, 0.0), REMEMBERED_FACTOR);
+        assert_eq!(explored.factor(&unseen, 0.0), 0.0);
+        assert!(explored.is_seen(&seen));
+    }
+
+    #[test]
+    pub fn full_reveal_test() {
+        let mut explored = ExploredTiles::new(FogMemory::FullReveal);
+        let t = TileTransform::new(WIDTH - 1, HEIGHT - 1);
+
+        assert!(explored.is_seen(&t));
+        assert_eq!(explored.factor(&t, 0.0), REMEMBERED_FACTOR);
+        assert_eq!(
+            explored.factor(&TileTransform::new(-1, 0), 0.0),
+            0.0,
+            "Tiles off the map are never remembered"
+        );
+    }
+}
@@ -1,150 +1,365 @@
-use lonely_tribes_components::{point_light::PointLight, tile_transform::TileTransform};
-use lonely_tribes_lib::{CONFIG, HEIGHT, WIDTH};
-use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use std::{collections::HashMap, sync::mpsc::channel};
+use crate::shadowcasting::get_visible_cells;
+use lonely_tribes_components::{
+    occupancy::{index_to_tile, tile_index, TileGrid},
+    point_light::PointLight,
+    tile_transform::TileTransform,
+};
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-#[derive(Default, Clone)]
-pub struct LightCacher {
-    pub current: Option<LightingData>,
+///Number of tiles in the light grid
+const GRID_SIZE: usize = (WIDTH * HEIGHT) as usize;
+
+///Any summed factor below this is treated as 0.0, to stop floating point errors building up from adding and removing lights
+const FACTOR_EPSILON: f32 = 1e-4;
+
+///How lit a tile is, and what colour the light on it is
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TileLight {
+    ///How lit the tile is, from 0.0 to 1.0
+    pub factor: f32,
+    ///The colour of the light, blended from every light that reaches the tile
+    pub colour: [f32; 3],
 }
-#[derive(Clone, Default)]
-pub struct LightingData {
-    pub tints: HashMap<TileTransform, f32>,
-    pub lights: Vec<TileTransform>,
-    pub colls: Vec<TileTransform>,
+impl Default for TileLight {
+    fn default() -> Self {
+        Self {
+            factor: 0.0,
+            colour: [1.0; 3],
+        }
+    }
 }
-impl PartialEq for LightingData {
-    fn eq(&self, other: &Self) -> bool {
-        self.lights == other.lights && self.colls == other.colls
+impl TileLight {
+    ///Constructor for a tile lit with white light
+    pub fn white(factor: f32) -> Self {
+        Self {
+            factor,
+            ..Default::default()
+        }
+    }
+
+    ///Gets the blended light from the sums of every light reaching a tile
+    ///
+    ///The factor is clamped to 1.0
+    ///
+    ///  - **total** is the sum of the factors
+    ///  - **weighted_colour** is the sum of the colours, each multiplied by its factor
+    pub fn from_sums(total: f32, weighted_colour: [f32; 3]) -> Self {
+        if total <= FACTOR_EPSILON {
+            return Self::default();
+        }
+
+        Self {
+            factor: total.min(1.0),
+            colour: [
+                weighted_colour[0] / total,
+                weighted_colour[1] / total,
+                weighted_colour[2] / total,
+            ],
+        }
     }
 }
-impl Eq for LightingData {}
 
-impl LightCacher {
-    fn get_lighted_cells_no_cache(
-        light: TileTransform,
-        rad: i32,
-        _colls: &[TileTransform],
-    ) -> Vec<TileTransform> {
-        let (sender, receiver) = channel();
-
-        (-rad..rad)
-            .into_par_iter()
-            .for_each_with(sender, |sender, i| {
-                let i = i as i32;
-                if !(light.x + i < 0 || light.x + i >= WIDTH as i32) {
-                    let (tx, rx) = channel();
-
-                    (-rad..rad).into_par_iter().for_each_with(tx, |tx, j| {
-                        let j = j as i32;
-                        if !(light.y + j < 0 || light.y + j >= HEIGHT as i32) {
-                            let delta = TileTransform::new(i, j);
-                            let pos = light + delta;
-
-                            if delta.get_magnitude() < rad as f32 {
-                                tx.send(pos).unwrap_or_else(|err| {
-                                    log::warn!("Couldn't send position to cells to test: {}", err)
-                                });
-                            }
-                        }
-                    });
-
-                    for item in rx.iter() {
-                        sender.send(item).unwrap_or_else(|err| {
-                            log::warn!("Couldn't send position to cells to test: {}", err)
-                        });
-                    }
+///A light, and every cell it lit the last time it was calculated
+#[derive(Clone, Debug)]
+struct CachedLight {
+    pos: TileTransform,
+    light: PointLight,
+    ///Grid index and factor for every cell it lights
+    cells: Vec<(usize, f32)>,
+}
+impl CachedLight {
+    ///Works out which cells a light reaches, without going through any colliders
+    ///
+    ///  - **pos** is the position of the light
+    ///  - **light** is the light
+    ///  - **colls** is a grid of where the colliders are
+    fn new(pos: TileTransform, light: PointLight, colls: &TileGrid<bool>) -> Self {
+        let cells = get_visible_cells(pos, light.radius as i32, |t| colls.contains(&t))
+            .into_iter()
+            .filter_map(|t| {
+                let fac = light.contribution(t.distance(&pos));
+                match tile_index(&t) {
+                    Some(i) if fac > 0.0 => Some((i, fac)),
+                    _ => None,
                 }
-            });
+            })
+            .collect();
+
+        Self { pos, light, cells }
+    }
 
-        let mut list: Vec<TileTransform> = receiver.iter().collect();
-        list.push(light);
-        //TODO: Shadows
-        list
+    ///Checks whether or not a collider at a tile could change what this light reaches
+    fn could_see(&self, t: &TileTransform) -> bool {
+        t.distance(&self.pos) <= self.light.radius as f32 + 1.0
     }
+}
+
+///Keeps track of the lighting for every tile in a dense grid
+///
+///Each light remembers the cells it lit, so only lights which have moved, changed, or had a collider change nearby get recalculated
+#[derive(Clone)]
+pub struct LightCacher {
+    ///Every light from the last update
+    lights: Vec<CachedLight>,
+    ///Every collider from the last update
+    colls: TileGrid<bool>,
+    ///Sum of the factors from every light, for each tile
+    factors: Vec<f32>,
+    ///Sum of the colours from every light, weighted by factor, for each tile
+    colours: Vec<[f32; 3]>,
+    ///Current lighting for each tile
+    grid: Vec<TileLight>,
+    ///Whether or not Fog Of War was enabled in the last update - None if there hasn't been one
+    fow_enabled: Option<bool>,
+}
+impl Default for LightCacher {
+    fn default() -> Self {
+        Self {
+            lights: Vec::new(),
+            colls: TileGrid::new(false),
+            factors: vec![0.0; GRID_SIZE],
+            colours: vec![[0.0; 3]; GRID_SIZE],
+            grid: vec![TileLight::default(); GRID_SIZE],
+            fow_enabled: None,
+        }
+    }
+}
+
+impl LightCacher {
+    ///Gets the current lighting for a tile
+    pub fn get(&self, t: &TileTransform) -> TileLight {
+        tile_index(t).map_or_else(TileLight::default, |i| self.grid[i])
+    }
+
+    ///Adds or removes the contributions from a light
+    ///
+    ///  - **light** is the light
+    ///  - **sign** is 1.0 to add it, or -1.0 to remove it
+    ///  - **touched** is set to true for every cell the light reaches
+    fn apply(&mut self, light: &CachedLight, sign: f32, touched: &mut [bool]) {
+        for (i, fac) in light.cells.iter().copied() {
+            self.factors[i] += sign * fac;
+            for (c, lc) in self.colours[i].iter_mut().zip(light.light.colour.iter()) {
+                *c += sign * fac * lc;
+            }
 
-    pub fn get_lighted_cells(
+            if self.factors[i] < FACTOR_EPSILON {
+                self.factors[i] = 0.0;
+                self.colours[i] = [0.0; 3];
+            }
+            touched[i] = true;
+        }
+    }
+
+    ///Updates the lighting, and gets every tile whose lighting has changed
+    ///
+    ///  - **lights** is a list of all the lights
+    ///  - **colls** is a grid of where the colliders are
+    ///  - **fow_enabled** is whether or not Fog Of War is enabled - if not, every cell is fully lit
+    pub fn update(
         &mut self,
         lights: &[(TileTransform, PointLight)],
-        colls: &[TileTransform],
-    ) -> HashMap<TileTransform, f32> {
-        let fow_enabled = CONFIG.flags.fow_enabled();
-
-        if !fow_enabled {
-            let mut hm = HashMap::new();
-            for x in 0..WIDTH {
-                for y in 0..HEIGHT {
-                    hm.insert(TileTransform::from((x, y)), 1.0);
+        colls: &TileGrid<bool>,
+        fow_enabled: bool,
+    ) -> Vec<TileTransform> {
+        let mut full_refresh = false;
+        if self.fow_enabled != Some(fow_enabled) {
+            //keep the old grid, so we can tell which tiles have changed
+            let grid = std::mem::take(&mut self.grid);
+            *self = Self {
+                fow_enabled: Some(fow_enabled),
+                grid,
+                ..Default::default()
+            };
+
+            if !fow_enabled {
+                let lit = TileLight::white(1.0);
+                let mut changed = Vec::new();
+                for (i, tl) in self.grid.iter_mut().enumerate() {
+                    if *tl != lit {
+                        *tl = lit;
+                        changed.push(index_to_tile(i));
+                    }
                 }
+                return changed;
             }
-
-            return hm;
+            full_refresh = true;
+        } else if !fow_enabled {
+            return Vec::new();
         }
 
-        let converted_lights = lights.iter().map(|(t, _)| *t).collect();
-        let converted_colls = Vec::from(colls);
+        let changed_colls: Vec<TileTransform> = if colls == &self.colls {
+            Vec::new()
+        } else {
+            let changed = colls
+                .as_slice()
+                .iter()
+                .zip(self.colls.as_slice())
+                .enumerate()
+                .filter(|(_, (nu, old))| nu != old)
+                .map(|(i, _)| index_to_tile(i))
+                .collect();
+            self.colls = colls.clone();
+            changed
+        };
 
-        if let Some(data) = &self.current {
-            if data.lights == converted_lights && data.colls == converted_colls {
-                return data.tints.clone();
+        //keep every light which is exactly the same, and couldn't be affected by a changed collider
+        let mut old_lights = std::mem::take(&mut self.lights);
+        let mut kept = Vec::with_capacity(lights.len());
+        let mut dirty = Vec::new();
+        for (pos, light) in lights {
+            let found = old_lights.iter().position(|c| {
+                c.pos == *pos && c.light == *light && !changed_colls.iter().any(|t| c.could_see(t))
+            });
+
+            match found {
+                Some(index) => kept.push(old_lights.swap_remove(index)),
+                None => dirty.push((*pos, *light)),
             }
         }
 
-        let (base_sender, base_receiver) = channel();
+        if old_lights.is_empty() && dirty.is_empty() && !full_refresh {
+            self.lights = kept;
+            return Vec::new();
+        }
 
-        lights
+        let mut touched = vec![full_refresh; GRID_SIZE];
+        for old in &old_lights {
+            self.apply(old, -1.0, &mut touched);
+        }
+
+        let colls = &self.colls;
+        let nu_lights: Vec<CachedLight> = dirty
             .par_iter()
-            .for_each_with(base_sender, |sender, (l_t_ref, l)| {
-                let l_t = *l_t_ref;
-                let (tx, rx) = channel();
-
-                Self::get_lighted_cells_no_cache(l_t, l.radius as i32, colls)
-                    .into_par_iter()
-                    .for_each_with(tx, |tx, t| {
-                        let try_fac = if fow_enabled {
-                            let dist = t.distance(l_t_ref);
-                            let rad = l.radius as f32;
-                            (rad - dist) / rad
-                        } else {
-                            1.0
-                        };
-
-                        tx.send((t, try_fac)).unwrap_or_else(|err| {
-                            log::warn!(
-                                "Error adding lighting factor to list for tile {}: {}",
-                                t,
-                                err
-                            )
-                        });
-                    });
-
-                for item in rx.iter() {
-                    sender.send(item).unwrap_or_else(|err| {
-                        log::warn!("Error adding list to lighting data list {}: {}", l_t, err)
-                    });
-                }
-            });
+            .map(|(pos, light)| CachedLight::new(*pos, *light, colls))
+            .collect();
+        for nu in &nu_lights {
+            self.apply(nu, 1.0, &mut touched);
+        }
+
+        kept.extend(nu_lights);
+        self.lights = kept;
+
+        let mut changed = Vec::new();
+        for (i, touched) in touched.into_iter().enumerate() {
+            if !touched {
+                continue;
+            }
+
+            let nu = TileLight::from_sums(self.factors[i], self.colours[i]);
+            if nu != self.grid[i] {
+                self.grid[i] = nu;
+                changed.push(index_to_tile(i));
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let mut hm = HashMap::new();
+    fn no_colls() -> TileGrid<bool> {
+        TileGrid::new(false)
+    }
 
-        for (tile, try_fac) in base_receiver.iter() {
-            let tile: TileTransform = tile;
-            let try_fac: f32 = try_fac;
+    #[test]
+    pub fn from_sums_test() {
+        assert_eq!(TileLight::from_sums(0.0, [0.0; 3]), TileLight::default());
 
-            let current_fac = hm.remove(&tile).unwrap_or(0.0);
-            let mut nu_fac = current_fac + try_fac;
-            if nu_fac > 1.0 {
-                nu_fac = 1.0;
+        let blended = TileLight::from_sums(1.0, [0.75, 0.0, 0.25]);
+        assert_eq!(blended.factor, 1.0);
+        assert_eq!(blended.colour, [0.75, 0.0, 0.25]);
+
+        let clamped = TileLight::from_sums(1.6, [1.6, 0.0, 0.0]);
+        assert_eq!(clamped.factor, 1.0);
+        assert_eq!(clamped.colour, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    pub fn coloured_lights_test() {
+        let mut cacher = LightCacher::default();
+        let light = PointLight {
+            colour: [0.0, 1.0, 0.0],
+            ..PointLight::new(3)
+        };
+        let pos = TileTransform::new(10, 10);
+        cacher.update(&[(pos, light)], &no_colls(), true);
+
+        assert_eq!(
+            cacher.get(&pos),
+            TileLight {
+                factor: 1.0,
+                colour: [0.0, 1.0, 0.0]
             }
-            hm.insert(tile, nu_fac);
+        );
+        assert_eq!(cacher.get(&TileTransform::new(13, 10)).factor, 0.0);
+    }
+
+    #[test]
+    pub fn incremental_update_test() {
+        let mut cacher = LightCacher::default();
+        let light = PointLight::new(3);
+        let a = TileTransform::new(10, 10);
+        let b = TileTransform::new(30, 10);
+
+        let changed = cacher.update(&[(a, light), (b, light)], &no_colls(), true);
+        assert!(!changed.is_empty());
+        assert!(cacher
+            .update(&[(a, light), (b, light)], &no_colls(), true)
+            .is_empty());
+
+        //moving one light shouldn't touch anything around the other one
+        let moved = TileTransform::new(11, 10);
+        let changed = cacher.update(&[(moved, light), (b, light)], &no_colls(), true);
+        assert!(!changed.is_empty());
+        assert!(changed.iter().all(|t| t.distance(&b) >= 3.0));
+
+        //the result should be the same as calculating it from scratch
+        let mut fresh = LightCacher::default();
+        fresh.update(&[(moved, light), (b, light)], &no_colls(), true);
+        for i in 0..GRID_SIZE {
+            let t = index_to_tile(i);
+            assert_eq!(cacher.get(&t), fresh.get(&t), "Different light at {}", t);
         }
 
-        self.current = Some(LightingData {
-            tints: hm.clone(),
-            lights: converted_lights,
-            colls: Vec::from(colls),
-        });
+        //removing every light should leave the grid dark again
+        cacher.update(&[], &no_colls(), true);
+        assert!((0..GRID_SIZE).all(|i| cacher.get(&index_to_tile(i)).factor == 0.0));
+    }
+
+    #[test]
+    pub fn collider_change_test() {
+        let mut cacher = LightCacher::default();
+        let light = PointLight::new(5);
+        let pos = TileTransform::new(10, 10);
+        let behind = TileTransform::new(13, 10);
+
+        cacher.update(&[(pos, light)], &no_colls(), true);
+        assert!(cacher.get(&behind).factor > 0.0);
+
+        let changed = cacher.update(
+            &[(pos, light)],
+            &TileGrid::from_tiles(&[TileTransform::new(12, 10)]),
+            true,
+        );
+        assert!(changed.contains(&behind));
+        assert_eq!(cacher.get(&behind).factor, 0.0);
+    }
+
+    #[test]
+    pub fn fow_disabled_test() {
+        let mut cacher = LightCacher::default();
+        let changed = cacher.update(&[], &no_colls(), false);
+        assert_eq!(changed.len(), GRID_SIZE);
+        assert_eq!(cacher.get(&TileTransform::new(5, 5)), TileLight::white(1.0));
+        assert!(cacher.update(&[], &no_colls(), false).is_empty());
 
-        hm
+        let changed = cacher.update(&[], &no_colls(), true);
+        assert_eq!(changed.len(), GRID_SIZE);
+        assert_eq!(cacher.get(&TileTransform::new(5, 5)).factor, 0.0);
     }
 }
@@ -1,2 +1,4 @@
+pub mod explored;
 pub mod fog;
 pub mod light_list;
+pub mod shadowcasting;
@@ -0,0 +1,189 @@
+use lonely_tribes_components::tile_transform::TileTransform;
+use lonely_tribes_lib::{HEIGHT, WIDTH};
+use std::collections::HashSet;
+
+///Multipliers to transform a cell in the first octant into each of the 8 octants
+///
+///Each one is (xx, xy, yx, yy)
+const OCTANTS: [(i32, i32, i32, i32); 8] = [
+    (1, 0, 0, 1),
+    (0, 1, 1, 0),
+    (0, -1, 1, 0),
+    (-1, 0, 0, 1),
+    (-1, 0, 0, -1),
+    (0, -1, -1, 0),
+    (0, 1, -1, 0),
+    (1, 0, 0, -1),
+];
+
+///Checks whether or not a cell is on the map
+fn in_bounds(t: TileTransform) -> bool {
+    t.x >= 0 && t.x < WIDTH && t.y >= 0 && t.y < HEIGHT
+}
+
+///Gets every cell which can be seen from an origin, using recursive shadowcasting
+///
+///Blocking cells are visible themselves, but nothing behind them is. Cells off the map are never visible, and block light.
+///
+///  - **origin** is the cell being seen from
+///  - **rad** is the radius - only cells strictly closer than it are visible
+///  - **blocks_light** checks whether a cell blocks light
+pub fn get_visible_cells<F: Fn(TileTransform) -> bool>(
+    origin: TileTransform,
+    rad: i32,
+    blocks_light: F,
+) -> HashSet<TileTransform> {
+    let mut visible = HashSet::new();
+    if !in_bounds(origin) {
+        return visible;
+    }
+    visible.insert(origin);
+
+    let is_blocking = |t: TileTransform| !in_bounds(t) || blocks_light(t);
+    for octant in OCTANTS.iter() {
+        cast_light(
+            &mut visible,
+            origin,
+            rad,
+            1,
+            (1.0, 0.0),
+            *octant,
+            &is_blocking,
+        );
+    }
+
+    visible
+}
+
+///Scans one octant row by row, recursing whenever a blocker splits the visible slopes
+///
+///  - **visible** is the set to add visible cells to
+///  - **origin** is the cell being seen from
+///  - **rad** is the radius
+///  - **row** is the row to start scanning from
+///  - **slopes** is the (start, end) slopes still visible in this octant
+///  - **octant** is the multipliers for this octant
+///  - **is_blocking** checks whether a cell blocks light
+fn cast_light<F: Fn(TileTransform) -> bool>(
+    visible: &mut HashSet<TileTransform>,
+    origin: TileTransform,
+    rad: i32,
+    row: i32,
+    (mut start, end): (f32, f32),
+    (xx, xy, yx, yy): (i32, i32, i32, i32),
+    is_blocking: &F,
+) {
+    if start < end {
+        return;
+    }
+
+    let rad_sq = rad * rad;
+    let mut new_start = 0.0;
+
+    for j in row..=rad {
+        let dy = -j;
+        let mut blocked = false;
+
+        for dx in -j..=0 {
+            let l_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let r_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+
+            if start < r_slope {
+                continue;
+            } else if end > l_slope {
+                break;
+            }
+
+            let cell =
+                TileTransform::new(origin.x + dx * xx + dy * xy, origin.y + dx * yx + dy * yy);
+            if dx * dx + dy * dy < rad_sq && in_bounds(cell) {
+                visible.insert(cell);
+            }
+
+            if blocked {
+                if is_blocking(cell) {
+                    new_start = r_slope;
+                } else {
+                    blocked = false;
+                    start = new_start;
+                }
+            } else if is_blocking(cell) && j < rad {
+                blocked = true;
+                cast_light(
+                    visible,
+                    origin,
+                    rad,
+                    j + 1,
+                    (start, l_slope),
+                    (xx, xy, yx, yy),
+                    is_blocking,
+                );
+                new_start = r_slope;
+            }
+        }
+
+        if blocked {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn open_field_test() {
+        let origin = TileTransform::new(20, 20);
+        let visible = get_visible_cells(origin, 5, |_| false);
+
+        for x in 10..30 {
+            for y in 10..30 {
+                let t = TileTransform::new(x, y);
+                assert_eq!(
+                    visible.contains(&t),
+                    t.distance(&origin) < 5.0,
+                    "Wrong visibility for {}",
+                    t
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn wall_blocks_test() {
+        let origin = TileTransform::new(20, 20);
+        let blockers = (15..26)
+            .map(|y| TileTransform::new(22, y))
+            .collect::<HashSet<_>>();
+        let visible = get_visible_cells(origin, 8, |t| blockers.contains(&t));
+
+        assert!(visible.contains(&TileTransform::new(21, 20)));
+        assert!(visible.contains(&TileTransform::new(22, 20)));
+        for x in 23..28 {
+            assert!(!visible.contains(&TileTransform::new(x, 20)));
+            assert!(!visible.contains(&TileTransform::new(x, 21)));
+        }
+        assert!(visible.contains(&TileTransform::new(17, 20)));
+    }
+
+    #[test]
+    pub fn pillar_shadow_test() {
+        let origin = TileTransform::new(20, 20);
+        let blockers = vec![TileTransform::new(20, 22)]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        let visible = get_visible_cells(origin, 8, |t| blockers.contains(&t));
+
+        assert!(visible.contains(&TileTransform::new(20, 22)));
+        assert!(!visible.contains(&TileTransform::new(20, 24)));
+        assert!(visible.contains(&TileTransform::new(23, 24)));
+    }
+
+    #[test]
+    pub fn edge_of_map_test() {
+        let visible = get_visible_cells(TileTransform::new(0, 0), 4, |_| false);
+        assert!(visible.iter().all(|t| in_bounds(*t)));
+        assert!(visible.contains(&TileTransform::new(3, 0)));
+    }
+}
@@ -1,22 +1,72 @@
 use crate::{procedural_generator::IS_DEMO, sprite_stuff::Room};
+use lonely_tribes_components::{
+    boss::BossConfig, fog_memory::FogMemory, hazards::HazardTimer, npcs::NpcPattern,
+    script::ScriptEntry, win_related::GamePlayingMode,
+};
 use lonely_tribes_lib::{either::Either, paths::get_directory};
 use ron::from_str;
 use serde::{Deserialize, Serialize};
 use std::fs::read_to_string;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct ReadInLevel {
+    ///Name of the level to show in the HUD - if None, one is made from the file name
+    #[serde(default)]
+    pub name: Option<String>,
+    ///How many moves the level should take, to show in the HUD
+    #[serde(default)]
+    pub par: Option<i32>,
     pub seed: Option<u32>,
     pub specials: usize,
     pub messages: Vec<(f32, String)>,
     pub is_csv: bool,
+    ///Name of the music track to play in this level - if None, one is picked automatically
+    #[serde(default)]
+    pub music: Option<String>,
+    ///How much of the map the fog of war remembers once it has been seen
+    #[serde(default)]
+    pub fog_memory: FogMemory,
+    ///Link ids for pressure plates, switches, gates and teleporters, as (x, y, link id) - these override any from a CSV file
+    #[serde(default)]
+    pub links: Vec<(usize, usize, usize)>,
+    ///Hazards which toggle on and off, as (x, y, moves between each toggle, offset in moves)
+    #[serde(default)]
+    pub timed_hazards: Vec<(usize, usize, u32, u32)>,
+    ///Whether or not the player picks one tribe at a time to move, rather than every tribe moving in lock-step
+    #[serde(default)]
+    pub independent_tribes: bool,
+    ///NPCs in the level, as (x, y, movement pattern)
+    #[serde(default)]
+    pub npcs: Vec<(usize, usize, NpcPattern)>,
+    ///The boss, for boss levels - these are won by defeating the boss rather than merging every tribe
+    #[serde(default)]
+    pub boss: Option<BossConfig>,
+    ///Which special modes can be used in this level - if None, every mode can be
+    #[serde(default)]
+    pub allowed_modes: Option<Vec<GamePlayingMode>>,
+    ///Modes which have their own pool of special moves rather than sharing `specials`, as (mode, moves in the pool)
+    #[serde(default)]
+    pub mode_budgets: Vec<(GamePlayingMode, i32)>,
+    ///Scripted events for the level, like tutorial messages, camera pans and highlighted tiles
+    #[serde(default)]
+    pub script: Vec<ScriptEntry>,
 }
 
 #[derive(Debug)]
 pub struct Level {
     pub room: Room,
+    pub name: Option<String>,
+    pub par: Option<i32>,
     pub specials: usize,
     pub messages: Vec<(f32, String)>,
+    pub music: Option<String>,
+    pub fog_memory: FogMemory,
+    pub independent_tribes: bool,
+    pub npcs: Vec<(usize, usize, NpcPattern)>,
+    pub boss: Option<BossConfig>,
+    pub allowed_modes: Option<Vec<GamePlayingMode>>,
+    pub mode_budgets: Vec<(GamePlayingMode, i32)>,
+    pub script: Vec<ScriptEntry>,
 }
 
 pub const RT_PROCGEN_FILENAME: &str = "runtime-procgen";
@@ -45,6 +95,24 @@ impl Level {
         }
     }
 
+    ///Gets the name to show for a level - its own name if it has one, or one made from its file name or seed
+    ///
+    ///  - **path** is the path the level was loaded from
+    ///  - **seed** is the seed the level was generated from, if it was
+    pub fn display_name(&self, path: &str, seed: Option<u32>) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        if let Some(seed) = seed {
+            return format!("Procgen - Seed {}", seed);
+        }
+
+        match Self::get_seed_index_from_path(path) {
+            Either::One(index) => format!("Level {}", index + 1),
+            Either::Two(seed) => format!("Procgen - Seed {}", seed),
+        }
+    }
+
     pub fn new(path: &str) -> (Self, Option<u32>) {
         if path.contains(RT_PROCGEN_FILENAME)
         //if we don't have a path, cos we are doing procgen now
@@ -53,6 +121,8 @@ impl Level {
             return (
                 Self {
                     room: Room::proc_gen(seed),
+                    name: None,
+                    par: None,
                     specials: 50,
                     messages: {
                         if IS_DEMO {
@@ -61,6 +131,14 @@ impl Level {
                             vec![]
                         }
                     },
+                    music: None,
+                    fog_memory: FogMemory::default(),
+                    independent_tribes: false,
+                    npcs: Vec::new(),
+                    boss: None,
+                    allowed_modes: None,
+                    mode_budgets: Vec::new(),
+                    script: Vec::new(),
                 },
                 Some(seed),
             );
@@ -72,13 +150,20 @@ impl Level {
 
         let r = match ril {
             Ok(ok) => {
-                let room = if let Some(s) = ok.seed {
+                let mut room = if let Some(s) = ok.seed {
                     Room::proc_gen(s)
                 } else if ok.is_csv {
                     Room::new(path.replace(".ron", ".csv"))
                 } else {
                     Room::new(path.replace(".ron", ".png"))
                 };
+                room.links
+                    .extend(ok.links.into_iter().map(|(x, y, link)| ((x, y), link)));
+                room.hazard_timers.extend(
+                    ok.timed_hazards
+                        .into_iter()
+                        .map(|(x, y, every, offset)| ((x, y), HazardTimer { every, offset })),
+                );
 
                 let mut msgs = ok.messages;
                 if IS_DEMO {
@@ -90,16 +175,36 @@ impl Level {
 
                 Self {
                     room,
+                    name: ok.name,
+                    par: ok.par,
                     specials: ok.specials,
                     messages: msgs,
+                    music: ok.music,
+                    fog_memory: ok.fog_memory,
+                    independent_tribes: ok.independent_tribes,
+                    npcs: ok.npcs,
+                    boss: ok.boss,
+                    allowed_modes: ok.allowed_modes,
+                    mode_budgets: ok.mode_budgets,
+                    script: ok.script,
                 }
             }
             Err(err) => {
                 log::warn!("Error reading in room: {} at path: {:?}", err, pathbuf);
                 Self {
                     room: Room::default(),
+                    name: None,
+                    par: None,
                     specials: 0,
                     messages: Vec::new(),
+                    music: None,
+                    fog_memory: FogMemory::default(),
+                    independent_tribes: false,
+                    npcs: Vec::new(),
+                    boss: None,
+                    allowed_modes: None,
+                    mode_budgets: Vec::new(),
+                    script: Vec::new(),
                 }
             }
         };
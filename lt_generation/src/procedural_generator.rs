@@ -1,5 +1,5 @@
 use crate::sprite_stuff::SpriteRequest;
-use lonely_tribes_components::tile_transform::TileTransform;
+use lonely_tribes_components::{occupancy::TileGrid, tile_transform::TileTransform};
 use lonely_tribes_lib::{scoped_timer::ScopedTimer, HEIGHT, WIDTH};
 use noise::{Fbm, NoiseFn, Seedable};
 use rand::{Rng, SeedableRng};
@@ -94,12 +94,20 @@ impl ProceduralGenerator {
         }
     }
 
-    fn find_blocked_bits(map: &MapSlice) -> Vec<(usize, usize)> {
-        map.to_owned()
-            .into_par_iter()
-            .filter(|(_, _, spr)| spr != &SpriteRequest::Blank && spr != &SpriteRequest::Door)
-            .map(|(x, y, _)| (x, y))
-            .collect()
+    fn find_blocked_bits(map: &MapSlice) -> TileGrid<bool> {
+        Self::find_tiles(map, |spr| {
+            spr != &SpriteRequest::Blank && spr != &SpriteRequest::Door
+        })
+    }
+
+    fn find_tiles(map: &MapSlice, filter: impl Fn(&SpriteRequest) -> bool) -> TileGrid<bool> {
+        let mut grid = TileGrid::new(false);
+        for (x, y, spr) in map {
+            if filter(spr) {
+                grid.set(&TileTransform::new(*x as i32, *y as i32), true);
+            }
+        }
+        grid
     }
 
     fn add_players(seed: u32, map: &mut Map) {
@@ -116,13 +124,14 @@ impl ProceduralGenerator {
             .into_iter()
             .enumerate();
 
-        let mut players = Vec::new();
+        let mut players = TileGrid::new(false);
         for (id, no) in no_players {
             for _ in 0..no {
                 loop {
                     let x = rng.gen_range(0..WIDTH as usize);
                     let y = rng.gen_range(0..HEIGHT as usize);
-                    if !blocked_bits.contains(&(x, y)) && !players.contains(&(x, y)) {
+                    let t = TileTransform::new(x as i32, y as i32);
+                    if !blocked_bits.contains(&t) && !players.contains(&t) {
                         let player = match id {
                             1 => SpriteRequest::Player1,
                             2 => SpriteRequest::Player2,
@@ -130,7 +139,7 @@ impl ProceduralGenerator {
                             _ => SpriteRequest::Player0,
                         };
 
-                        players.push((x, y));
+                        players.set(&t, true);
                         map.push((x, y, player));
                         break;
                     }
@@ -143,12 +152,7 @@ impl ProceduralGenerator {
         // let t = SystemTime::now();
 
         let blocked_bits = Self::find_blocked_bits(map);
-        let plant_places: Vec<(usize, usize)> = map
-            .clone()
-            .into_par_iter()
-            .filter(|(_, _, spr)| spr == &SpriteRequest::Door)
-            .map(|(x, y, _)| (x, y))
-            .collect();
+        let plant_places = Self::find_tiles(map, |spr| spr == &SpriteRequest::Door);
 
         let p1 = Fbm::new().set_seed(seed);
         let p2 = Fbm::new().set_seed(seed + 100);
@@ -169,7 +173,8 @@ impl ProceduralGenerator {
                         let no_1 = p1.get(p_val);
                         let no_2 = p2.get(p_val);
 
-                        let no_3 = if plant_places.contains(&(x, y)) {
+                        let pos = TileTransform::new(x as i32, y as i32);
+                        let no_3 = if plant_places.contains(&pos) {
                             if no_2 > SHRUBBERY_THRESHOLD {
                                 Some(1)
                             } else {
@@ -184,7 +189,7 @@ impl ProceduralGenerator {
 
                             let changer =
                                 |shrubbery: SpriteRequest, tree_spr: SpriteRequest, v: f64, must: bool| {
-                                    let blocked = blocked_bits.contains(&(x, y));
+                                    let blocked = blocked_bits.contains(&pos);
                                     if blocked && can_override {
                                         s.send((x, y, tree_spr)).unwrap_or_else(|err| log::warn!("Error with Multithreading for Proc Gen - in changer: {}", err));
                                     } else if !blocked {
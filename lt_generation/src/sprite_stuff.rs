@@ -1,6 +1,13 @@
 use crate::procedural_generator::ProceduralGenerator;
 use derive_try_from_primitive::TryFromPrimitive;
 use image::{GenericImageView, Rgba};
+use lonely_tribes_components::{
+    floors::{Floor, FloorEffect},
+    hazards::{Hazard, HazardKind, HazardTimer},
+    point_light::{LightFalloff, PointLight},
+    switches::Linked,
+    tile_transform::TileTransform,
+};
 use lonely_tribes_lib::{either::Either, paths::get_directory, HEIGHT, WIDTH};
 use lonely_tribes_tags::{
     tag::{
@@ -40,6 +47,27 @@ pub enum SpriteRequest {
     DarkShrubbery = 4,
     Tree = 50,
     WarpedTree = 102,
+    Torch = 495,
+    PushBlock = 344,
+    PressurePlate = 745,
+    PressurePlateDown = 746,
+    Switch = 743,
+    SwitchOn = 744,
+    Gate = 149,
+    GateOpen = 150,
+    Pit = 167,
+    Spikes = 22,
+    Water = 248,
+    HazardCovered = 168,
+    Teleporter = 1035,
+    OneWayUp = 1036,
+    OneWayRight = 1037,
+    OneWayDown = 1038,
+    OneWayLeft = 1039,
+    Npc = 402,
+    Boss = 413,
+    RespawnPoint = 988,
+    Highlight = 977,
     Blank = -1,
 }
 impl Default for SpriteRequest {
@@ -67,11 +95,94 @@ impl FromSpr for Tag {
             SpriteRequest::Player2 => Self::Player(2),
             SpriteRequest::Player3 => Self::Player(3),
             SpriteRequest::Door => Self::Trigger(TriggerType::Door),
+            SpriteRequest::PushBlock => Self::Pushable,
+            SpriteRequest::PressurePlate => Self::Trigger(TriggerType::PressurePlate(0)),
+            SpriteRequest::Switch => Self::Trigger(TriggerType::Switch(0)),
+            SpriteRequest::Gate => Self::Trigger(TriggerType::Gate(0)),
+            Pit | Spikes | Water => Self::Hazard,
+            Teleporter | OneWayUp | OneWayRight | OneWayDown | OneWayLeft => Self::Floor,
             Blank | Shrubbery | DarkShrubbery => Other,
             _ => Collision,
         }
     }
 }
+impl FromSpr for PointLight {
+    type Output = Option<Self>;
+    fn from_spr(spr: SpriteRequest) -> Option<Self> {
+        match spr {
+            SpriteRequest::Torch => Some(Self {
+                radius: 4,
+                colour: [1.0, 0.7, 0.4],
+                falloff: LightFalloff::Smooth,
+                flicker: 0.3,
+                ..Default::default()
+            }),
+            SpriteRequest::WarpedTree => Some(Self {
+                radius: 2,
+                colour: [0.7, 0.5, 1.0],
+                intensity: 0.6,
+                flicker: 0.1,
+                ..Default::default()
+            }),
+            _ => None,
+        }
+    }
+}
+impl FromSpr for Hazard {
+    type Output = Option<Self>;
+    ///Gets the hazard for a sprite, without a timer - when inactive they are covered up
+    fn from_spr(spr: SpriteRequest) -> Option<Self> {
+        let kind = match spr {
+            SpriteRequest::Pit => HazardKind::Pit,
+            SpriteRequest::Spikes => HazardKind::Spikes,
+            SpriteRequest::Water => HazardKind::Water,
+            _ => return None,
+        };
+
+        Some(Self::new(
+            kind,
+            [
+                SpriteRequest::HazardCovered as i32 as usize,
+                spr as i32 as usize,
+            ],
+        ))
+    }
+}
+impl FromSpr for Floor {
+    type Output = Option<Self>;
+    ///Gets the special floor for a sprite - teleporters have a link id of 0
+    fn from_spr(spr: SpriteRequest) -> Option<Self> {
+        let effect = match spr {
+            SpriteRequest::Teleporter => FloorEffect::Teleporter(0),
+            SpriteRequest::OneWayUp => FloorEffect::OneWay(TileTransform::new(0, -1)),
+            SpriteRequest::OneWayRight => FloorEffect::OneWay(TileTransform::new(1, 0)),
+            SpriteRequest::OneWayDown => FloorEffect::OneWay(TileTransform::new(0, 1)),
+            SpriteRequest::OneWayLeft => FloorEffect::OneWay(TileTransform::new(-1, 0)),
+            _ => return None,
+        };
+        Some(Self(effect))
+    }
+}
+impl FromSpr for Linked {
+    type Output = Option<Self>;
+    ///Gets the linked tile for a sprite, with a link id of 0
+    fn from_spr(spr: SpriteRequest) -> Option<Self> {
+        let (trigger, active) = match spr {
+            SpriteRequest::PressurePlate => (
+                TriggerType::PressurePlate(0),
+                SpriteRequest::PressurePlateDown,
+            ),
+            SpriteRequest::Switch => (TriggerType::Switch(0), SpriteRequest::SwitchOn),
+            SpriteRequest::Gate => (TriggerType::Gate(0), SpriteRequest::GateOpen),
+            _ => return None,
+        };
+
+        Some(Self::new(
+            trigger,
+            [spr as i32 as usize, active as i32 as usize],
+        ))
+    }
+}
 
 lazy_static! {
     static ref SPRITESHEET_SWATCH_HASHMAP: HashMap<Rgba<u8>, SpriteRequest> = {
@@ -101,6 +212,19 @@ lazy_static! {
         s(91, 110, 225, Player1);
         s(99, 155, 255, Player2);
         s(95, 205, 228, Player3);
+        s(203, 219, 252, Torch); //6
+        s(155, 173, 183, PushBlock);
+        s(132, 126, 135, PressurePlate);
+        s(105, 106, 106, Switch);
+        s(89, 86, 82, Gate);
+        s(118, 66, 138, Pit);
+        s(172, 50, 50, Spikes);
+        s(217, 87, 99, Water);
+        s(215, 123, 186, Teleporter);
+        s(143, 151, 74, OneWayUp);
+        s(138, 111, 48, OneWayRight);
+        s(55, 148, 110, OneWayDown);
+        s(255, 255, 255, OneWayLeft);
 
 
         map
@@ -140,6 +264,23 @@ lazy_static! {
             DarkShrubbery,
             Tree,
             WarpedTree,
+            Torch,
+            PushBlock,
+            PressurePlate,
+            PressurePlateDown,
+            Switch,
+            SwitchOn,
+            Gate,
+            GateOpen,
+            Pit,
+            Spikes,
+            Water,
+            HazardCovered,
+            Teleporter,
+            OneWayUp,
+            OneWayRight,
+            OneWayDown,
+            OneWayLeft,
         ]
     };
 
@@ -166,11 +307,17 @@ impl SpriteRequest {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Room {
     pub data: Vec<Vec<Either<SpriteRequest, i32>>>,
+    ///Link ids for pressure plates, switches, gates and teleporters, indexed by (x, y) - anything missing has a link id of 0
+    pub links: HashMap<(usize, usize), usize>,
+    ///Timers for hazards which toggle on and off, indexed by (x, y)
+    pub hazard_timers: HashMap<(usize, usize), HazardTimer>,
 }
 impl Default for Room {
     fn default() -> Self {
         Self {
             data: vec![vec![Either::One(SpriteRequest::Blank); WIDTH as usize]; HEIGHT as usize],
+            links: HashMap::new(),
+            hazard_timers: HashMap::new(),
         }
     }
 }
@@ -189,9 +336,13 @@ impl DerefMut for Room {
 }
 
 impl Room {
+    ///Reads in a room from an image, or from a CSV file
+    ///
+    ///In CSV files, a link id can be given after a colon, eg. `745:2` is a pressure plate with a link id of 2
     pub fn new(path: String) -> Self {
         let mut data =
             vec![vec![Either::One(SpriteRequest::Blank); HEIGHT as usize]; WIDTH as usize];
+        let mut links = HashMap::new();
         let path = get_directory(false).join("../maps").join(path);
         let path = path.to_str().unwrap_or_default();
 
@@ -211,10 +362,27 @@ impl Room {
 
                 for (y, line) in contents.lines().into_iter().enumerate() {
                     for (x, thing) in line.split(',').into_iter().enumerate() {
-                        let i = thing.parse().unwrap_or(-1);
+                        let mut parts = thing.trim().split(':');
+                        let i = parts.next().and_then(|i| i.parse().ok()).unwrap_or(-1);
                         if i == -1 {
                             continue;
                         }
+                        if let Some(link) = parts.next() {
+                            match link.parse() {
+                                Ok(link) => {
+                                    links.insert((x, y), link);
+                                }
+                                Err(err) => {
+                                    log::warn!(
+                                        "Invalid link id at ({}, {}) in {}: {}",
+                                        x,
+                                        y,
+                                        path,
+                                        err
+                                    )
+                                }
+                            }
+                        }
 
                         let spr = SpriteRequest::try_from(i);
                         data[x][y] = match spr {
@@ -226,7 +394,11 @@ impl Room {
             }
         };
 
-        Self { data }
+        Self {
+            data,
+            links,
+            hazard_timers: HashMap::new(),
+        }
     }
 
     pub fn proc_gen(seed: u32) -> Self {
@@ -239,6 +411,10 @@ impl Room {
             data[x][y] = Either::One(spr);
         });
 
-        Self { data }
+        Self {
+            data,
+            links: HashMap::new(),
+            hazard_timers: HashMap::new(),
+        }
     }
 }
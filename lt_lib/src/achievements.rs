@@ -0,0 +1,330 @@
+use crate::paths::get_directory;
+use ron::{from_str, to_string};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fs::{read_to_string, write},
+    path::PathBuf,
+};
+
+///All of the achievements in the game
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    ///Beat any level
+    FirstWin,
+    ///Beat a level without using any special modes
+    NoSpecials,
+    ///Merge 20 tribe members in total
+    MergeTwenty,
+    ///Beat 10 different procedurally generated seeds
+    TenSeeds,
+}
+
+impl Achievement {
+    ///All of the achievements, in the order they are shown in
+    pub const ALL: [Achievement; 4] = [
+        Achievement::FirstWin,
+        Achievement::NoSpecials,
+        Achievement::MergeTwenty,
+        Achievement::TenSeeds,
+    ];
+
+    ///Gets the name to show to the player
+    pub fn name(&self) -> &'static str {
+        use Achievement::*;
+        match self {
+            FirstWin => "Lonely No More",
+            NoSpecials => "Purist",
+            MergeTwenty => "Big Family",
+            TenSeeds => "Explorer",
+        }
+    }
+
+    ///Gets the description to show to the player
+    pub fn description(&self) -> &'static str {
+        use Achievement::*;
+        match self {
+            FirstWin => "Beat a level",
+            NoSpecials => "Beat a level without using any special modes",
+            MergeTwenty => "Merge 20 tribe members",
+            TenSeeds => "Beat 10 different procedurally generated seeds",
+        }
+    }
+
+    ///Gets the API name for the achievement, as set up on Steam
+    pub fn api_name(&self) -> &'static str {
+        use Achievement::*;
+        match self {
+            FirstWin => "ACH_FIRST_WIN",
+            NoSpecials => "ACH_NO_SPECIALS",
+            MergeTwenty => "ACH_MERGE_TWENTY",
+            TenSeeds => "ACH_TEN_SEEDS",
+        }
+    }
+
+    ///Checks whether or not the stats are enough for the achievement
+    ///
+    ///Achievements which are unlocked by one specific thing happening always return false
+    pub fn is_earned_by(&self, stats: &Stats) -> bool {
+        use Achievement::*;
+        match self {
+            FirstWin => stats.levels_won >= 1,
+            NoSpecials => false,
+            MergeTwenty => stats.tribes_merged >= 20,
+            TenSeeds => stats.seeds_beaten.len() >= 10,
+        }
+    }
+}
+
+///Stats kept across every game, which some achievements depend on
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Stats {
+    ///Number of levels won
+    pub levels_won: u32,
+    ///Number of times two members of a tribe have merged
+    pub tribes_merged: u32,
+    ///All of the different procgen seeds that have been beaten
+    pub seeds_beaten: Vec<u32>,
+}
+
+fn stats_path() -> PathBuf {
+    get_directory(false).join("stats.ron")
+}
+
+impl Stats {
+    ///Reads in the stats from the stats file, or makes new stats if there aren't any
+    pub fn new() -> Self {
+        let file = read_to_string(stats_path()).unwrap_or_default();
+        from_str(&file).unwrap_or_default()
+    }
+
+    ///Writes the stats to the stats file
+    pub fn save(&self) {
+        match to_string(self) {
+            Ok(s) => write(stats_path(), s)
+                .unwrap_or_else(|err| log::warn!("Unable to write stats: {}", err)),
+            Err(err) => log::warn!("Unable to serialise stats: {}", err),
+        }
+    }
+
+    ///Adds a procgen seed to the beaten seeds, if it isn't already there
+    pub fn add_seed(&mut self, seed: u32) {
+        if !self.seeds_beaten.contains(&seed) {
+            self.seeds_beaten.push(seed);
+        }
+    }
+}
+
+///Trait for somewhere that achievements get unlocked - eg. a local file, or Steam
+pub trait AchievementBackend: Send + Sync {
+    ///Unlocks an achievement
+    fn unlock(&mut self, achievement: Achievement);
+    ///Checks whether an achievement has been unlocked
+    fn is_unlocked(&self, achievement: Achievement) -> bool;
+    ///Saves any unlocked achievements
+    fn store(&mut self);
+}
+
+fn local_achievements_path() -> PathBuf {
+    get_directory(false).join("achievements.ron")
+}
+
+///Backend which keeps achievements in a file, so it works without Steam running
+#[derive(Default, Debug)]
+pub struct LocalAchievements {
+    unlocked: HashSet<Achievement>,
+}
+
+impl LocalAchievements {
+    ///Reads in the unlocked achievements from the achievements file
+    pub fn new() -> Self {
+        let file = read_to_string(local_achievements_path()).unwrap_or_default();
+        Self {
+            unlocked: from_str(&file).unwrap_or_default(),
+        }
+    }
+}
+
+impl AchievementBackend for LocalAchievements {
+    fn unlock(&mut self, achievement: Achievement) {
+        self.unlocked.insert(achievement);
+    }
+
+    fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    fn store(&mut self) {
+        match to_string(&self.unlocked) {
+            Ok(s) => write(local_achievements_path(), s)
+                .unwrap_or_else(|err| log::warn!("Unable to write achievements: {}", err)),
+            Err(err) => log::warn!("Unable to serialise achievements: {}", err),
+        }
+    }
+}
+
+///Backend which unlocks achievements on Steam, as well as keeping them locally so the viewer works offline
+#[cfg(feature = "steam")]
+pub struct SteamAchievements {
+    client: steamworks::Client,
+    local: LocalAchievements,
+}
+#[cfg(feature = "steam")]
+unsafe impl Sync for SteamAchievements {}
+#[cfg(feature = "steam")]
+unsafe impl Send for SteamAchievements {}
+
+#[cfg(feature = "steam")]
+impl SteamAchievements {
+    ///Constructor for SteamAchievements
+    ///
+    ///  - **client** is the Steam client to unlock achievements with
+    pub fn new(client: steamworks::Client) -> Self {
+        Self {
+            client,
+            local: LocalAchievements::new(),
+        }
+    }
+}
+
+#[cfg(feature = "steam")]
+impl AchievementBackend for SteamAchievements {
+    fn unlock(&mut self, achievement: Achievement) {
+        self.local.unlock(achievement);
+        self.client
+            .user_stats()
+            .achievement(achievement.api_name())
+            .set()
+            .unwrap_or_else(|_| {
+                log::warn!("Unable to unlock {:?} on Steam", achievement);
+            });
+    }
+
+    fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.local.is_unlocked(achievement)
+            || self
+                .client
+                .user_stats()
+                .achievement(achievement.api_name())
+                .get()
+                .unwrap_or(false)
+    }
+
+    fn store(&mut self) {
+        self.local.store();
+        self.client
+            .user_stats()
+            .store_stats()
+            .unwrap_or_else(|_| log::warn!("Unable to store stats on Steam"));
+    }
+}
+
+///Resource to hold the achievements backend, as well as the stats
+pub struct Achievements {
+    backend: Box<dyn AchievementBackend>,
+    pub stats: Stats,
+}
+
+impl Achievements {
+    ///Constructor for Achievements, which reads in the stats from the stats file
+    ///
+    ///  - **backend** is where achievements get unlocked
+    pub fn new(backend: Box<dyn AchievementBackend>) -> Self {
+        Self {
+            backend,
+            stats: Stats::new(),
+        }
+    }
+
+    ///Unlocks an achievement
+    ///
+    ///Returns true if it wasn't already unlocked
+    pub fn unlock(&mut self, achievement: Achievement) -> bool {
+        if self.backend.is_unlocked(achievement) {
+            false
+        } else {
+            log::info!("Unlocked achievement: {:?}", achievement);
+            self.backend.unlock(achievement);
+            true
+        }
+    }
+
+    ///Checks whether an achievement has been unlocked
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.backend.is_unlocked(achievement)
+    }
+
+    ///Unlocks any achievements which the stats are now enough for
+    ///
+    ///Returns all of the newly unlocked achievements
+    pub fn check_stats(&mut self) -> Vec<Achievement> {
+        let stats = self.stats.clone();
+        Achievement::ALL
+            .iter()
+            .copied()
+            .filter(|a| a.is_earned_by(&stats))
+            .filter(|a| self.unlock(*a))
+            .collect()
+    }
+
+    ///Writes the stats and achievements
+    pub fn save(&mut self) {
+        self.stats.save();
+        self.backend.store();
+    }
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Self::new(Box::new(LocalAchievements::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn stats_test() {
+        let mut stats = Stats::default();
+        assert!(!Achievement::FirstWin.is_earned_by(&stats));
+
+        stats.levels_won = 1;
+        stats.tribes_merged = 19;
+        assert!(Achievement::FirstWin.is_earned_by(&stats));
+        assert!(!Achievement::MergeTwenty.is_earned_by(&stats));
+
+        stats.tribes_merged += 1;
+        assert!(Achievement::MergeTwenty.is_earned_by(&stats));
+    }
+
+    #[test]
+    pub fn seeds_test() {
+        let mut stats = Stats::default();
+        for _ in 0..10 {
+            stats.add_seed(42);
+        }
+        assert_eq!(stats.seeds_beaten.len(), 1);
+
+        for seed in 0..9 {
+            stats.add_seed(seed);
+        }
+        assert!(Achievement::TenSeeds.is_earned_by(&stats));
+    }
+
+    #[test]
+    pub fn unlock_once_test() {
+        let mut achievements = Achievements {
+            backend: Box::new(LocalAchievements::default()),
+            stats: Stats::default(),
+        };
+
+        assert!(achievements.unlock(Achievement::NoSpecials));
+        assert!(!achievements.unlock(Achievement::NoSpecials));
+        assert!(achievements.is_unlocked(Achievement::NoSpecials));
+
+        achievements.stats.levels_won = 1;
+        assert_eq!(achievements.check_stats(), vec![Achievement::FirstWin]);
+        assert!(achievements.check_stats().is_empty());
+    }
+}
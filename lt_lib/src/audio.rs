@@ -1,50 +1,330 @@
 use crate::config::LTConfig;
 use amethyst::{
     assets::Loader,
-    audio::{AudioSink, Mp3Format, SourceHandle},
+    audio::{Mp3Format, SourceHandle, WavFormat},
     ecs::{World, WorldExt},
 };
-use std::{iter::Cycle, vec::IntoIter};
-
-const BACKGROUND_TRACKS: &[&str] = &[
-    "music/DOS-88/Checking Manifest.mp3",
-    "music/DOS-88/DOS-88 - Far Away.mp3",
-    "music/DOS-88/Parabola.mp3",
-    "music/DOS-88/Race to Mars.mp3",
-    "music/DOS-88/Smooth Sailing.mp3",
+use std::collections::HashMap;
+
+///Folder that all of the music lives in
+const MUSIC_DIR: &str = "music/LOST";
+
+///All of the music tracks, as (name, intro file, loop file)
+///
+///If a track has an intro, that is played once before the loop starts, otherwise the loop file is just looped.
+const TRACKS: &[(&str, Option<&str>, &str)] = &[
+    (
+        "Alone",
+        Some("Alone - melancholic - slow.mp3"),
+        "Alone(loop) - melancholic - slow.mp3",
+    ),
+    ("Anormal", None, "Anormal - calming - ambient.mp3"),
+    ("Closing", None, "Closing - strings - peaceful.mp3"),
+    ("Concern", None, "Concern - drone - anxiety.mp3"),
+    (
+        "Echo",
+        Some("Echo - hollow - scary - anxiety.mp3"),
+        "Echo(loop) - hollow - scary - anxiety.mp3",
+    ),
+    (
+        "Lost",
+        Some("Lost - drone - dark.mp3"),
+        "Lost(loop) - drone - dark.mp3",
+    ),
+    ("Room", None, "Room(Loop) - piano - drone.mp3"),
+    ("Stars", None, "Stars - ambient - guitar - piano.mp3"),
+    (
+        "Transfer",
+        Some("Transfer - drone - spaces.mp3"),
+        "Transfer(loop) - drone - spaces.mp3",
+    ),
 ];
 
+///Folder that all of the sound effects live in
+const SFX_DIR: &str = "sfx";
+
+///Volume of the music at full volume in the config
+pub const MUSIC_VOLUME: f32 = 0.25;
+///Volume of the sound effects at full volume in the config
+pub const SFX_VOLUME: f32 = 0.5;
+///How long it takes to crossfade between two tracks, in seconds
+pub const CROSSFADE_TIME: f32 = 2.0;
+
+///What is currently happening in the game, which decides what music plays
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MusicContext {
+    ///Any of the menus
+    Menu,
+    ///Normal gameplay
+    Puzzle,
+    ///Gameplay, with a special mode active
+    SpecialMode,
+    ///The zoom-in after losing a level
+    LossZoom,
+    ///The screen after finishing every level
+    TrueEnd,
+}
+
+impl Default for MusicContext {
+    fn default() -> Self {
+        Self::Menu
+    }
+}
+
+impl MusicContext {
+    ///Gets the names of the tracks that suit this context
+    pub fn tracks(&self) -> &'static [&'static str] {
+        use MusicContext::*;
+        match self {
+            Menu => &["Stars", "Anormal"],
+            Puzzle => &["Alone", "Transfer", "Room", "Lost"],
+            SpecialMode => &["Echo"],
+            LossZoom => &["Concern"],
+            TrueEnd => &["Closing"],
+        }
+    }
+}
+
+///A loaded music track
+#[derive(Clone)]
+pub struct MusicTrack {
+    ///Part which gets played once at the start, if there is one
+    pub intro: Option<SourceHandle>,
+    ///Part which gets looped
+    pub looped: SourceHandle,
+}
+
+///Resource to hold all of the music, and what should currently be playing
+///
+///States set the context, and the MusicDirectorSystem actually plays the music
+#[derive(Default)]
 pub struct Muzac {
-    pub music: Cycle<IntoIter<SourceHandle>>,
+    ///All of the loaded tracks, by name
+    tracks: HashMap<String, MusicTrack>,
+    ///What is currently happening in the game
+    context: MusicContext,
+    ///The music that the current level asked for, if any
+    level_track: Option<String>,
+    ///Used to vary which track gets picked for a context, bumped every level
+    variant: usize,
+}
+
+impl Muzac {
+    ///Sets the current context
+    pub fn set_context(&mut self, context: MusicContext) {
+        if self.context != context {
+            log::info!("Music context changed to {:?}", context);
+            self.context = context;
+        }
+    }
+
+    ///Gets the current context
+    pub fn context(&self) -> MusicContext {
+        self.context
+    }
+
+    ///Sets the music for a new level
+    ///
+    ///  - **track** is the name of the track the level asked for, or None to pick one based on the context
+    pub fn set_level_track(&mut self, track: Option<String>) {
+        if let Some(t) = &track {
+            if !TRACKS.iter().any(|(name, _, _)| name == t) {
+                log::warn!("Level asked for unknown music track: {}", t);
+            }
+        }
+
+        self.level_track = track;
+        self.variant = self.variant.wrapping_add(1);
+    }
+
+    ///Gets the name of the track that should currently be playing
+    pub fn desired_track(&self) -> &str {
+        if self.context == MusicContext::Puzzle {
+            if let Some(t) = &self.level_track {
+                if let Some((name, _, _)) = TRACKS.iter().find(|(name, _, _)| name == t) {
+                    return name;
+                }
+            }
+        }
+
+        let tracks = self.context.tracks();
+        tracks[self.variant % tracks.len()]
+    }
+
+    ///Gets a loaded track by name - will be None if audio is disabled
+    pub fn get_track(&self, name: &str) -> Option<&MusicTrack> {
+        self.tracks.get(name)
+    }
+}
+
+///A sound effect for something happening in the game
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    ///A tribe moved one step
+    Step,
+    ///A tribe tried to move, but walked into a collider
+    Bump,
+    ///Two members of the same tribe merged
+    Merge,
+    ///A special mode was turned on
+    ModeEnter,
+    ///A special mode was turned off, or ran out
+    ModeLeave,
+    ///The level was lost
+    Lose,
+    ///The level was won
+    Win,
+    ///The mouse moved over a button
+    UiHover,
+    ///A button was clicked
+    UiClick,
+}
+
+impl SoundEffect {
+    ///All of the sound effects, for loading
+    pub const ALL: [SoundEffect; 9] = [
+        SoundEffect::Step,
+        SoundEffect::Bump,
+        SoundEffect::Merge,
+        SoundEffect::ModeEnter,
+        SoundEffect::ModeLeave,
+        SoundEffect::Lose,
+        SoundEffect::Win,
+        SoundEffect::UiHover,
+        SoundEffect::UiClick,
+    ];
+
+    ///Gets the file name of the sound effect, inside the sfx folder
+    pub fn file(&self) -> &'static str {
+        use SoundEffect::*;
+        match self {
+            Step => "step.wav",
+            Bump => "bump.wav",
+            Merge => "merge.wav",
+            ModeEnter => "mode_enter.wav",
+            ModeLeave => "mode_leave.wav",
+            Lose => "lose.wav",
+            Win => "win.wav",
+            UiHover => "ui_hover.wav",
+            UiClick => "ui_click.wav",
+        }
+    }
+}
+
+///Resource to queue up sound effects to be played - anything can push to this, and the SfxSystem plays and clears it every frame
+#[derive(Default, Debug)]
+pub struct SfxQueue(pub Vec<SoundEffect>);
+
+///Resource to hold all of the loaded sound effects - only exists if the sound effects are enabled
+pub struct Sfx {
+    sounds: HashMap<SoundEffect, SourceHandle>,
+}
+
+impl Sfx {
+    ///Gets a loaded sound effect
+    pub fn get(&self, sfx: SoundEffect) -> Option<&SourceHandle> {
+        self.sounds.get(&sfx)
+    }
 }
 
+///Loads in all of the music and sound effects, if they aren't already loaded
 pub fn init_audio(world: &mut World) {
-    let vol = LTConfig::new().conf.vol;
+    let (vol, sfx_vol) = {
+        let conf = world.read_resource::<LTConfig>().conf;
+        (conf.vol, conf.sfx_vol)
+    };
+
+    if sfx_vol > 0.0 && world.try_fetch::<Sfx>().is_none() {
+        let sounds = {
+            let loader = world.read_resource::<Loader>();
+            SoundEffect::ALL
+                .iter()
+                .map(|sfx| {
+                    let handle = loader.load(
+                        format!("{}/{}", SFX_DIR, sfx.file()),
+                        WavFormat,
+                        (),
+                        &world.read_resource(),
+                    );
+                    (*sfx, handle)
+                })
+                .collect()
+        };
+        world.insert(Sfx { sounds });
+    }
 
     if vol <= 0.0 {
         return;
     }
+    if world
+        .try_fetch::<Muzac>()
+        .map(|m| !m.tracks.is_empty())
+        .unwrap_or(false)
+    {
+        return;
+    }
 
-    let music = {
+    let tracks = {
         let loader = world.read_resource::<Loader>();
 
-        let mut sink = world.write_resource::<AudioSink>();
-        sink.set_volume(0.25 * vol);
-
-        let music = BACKGROUND_TRACKS
+        TRACKS
             .iter()
-            .map(|file| load_audio_track(&loader, world, file))
-            .collect::<Vec<_>>()
-            .into_iter()
-            .cycle();
-
-        Muzac { music }
+            .map(|(name, intro, looped)| {
+                let track = MusicTrack {
+                    intro: intro.map(|file| {
+                        load_audio_track(&loader, world, &format!("{}/{}", MUSIC_DIR, file))
+                    }),
+                    looped: load_audio_track(&loader, world, &format!("{}/{}", MUSIC_DIR, looped)),
+                };
+                (name.to_string(), track)
+            })
+            .collect::<HashMap<_, _>>()
     };
 
-    world.insert(music);
+    world.entry::<Muzac>().or_insert_with(Muzac::default).tracks = tracks;
+}
+
+///Sets what the music should be reacting to, from a state
+pub fn set_music_context(world: &mut World, context: MusicContext) {
+    world
+        .entry::<Muzac>()
+        .or_insert_with(Muzac::default)
+        .set_context(context);
 }
 
 #[inline(always)]
 pub fn load_audio_track(loader: &Loader, world: &World, file: &str) -> SourceHandle {
     loader.load(file, Mp3Format, (), &world.read_resource())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn context_tracks_exist_test() {
+        use MusicContext::*;
+        for context in &[Menu, Puzzle, SpecialMode, LossZoom, TrueEnd] {
+            for t in context.tracks() {
+                assert!(TRACKS.iter().any(|(name, _, _)| name == t));
+            }
+        }
+    }
+
+    #[test]
+    pub fn level_track_test() {
+        let mut muzac = Muzac::default();
+        muzac.set_context(MusicContext::Puzzle);
+        muzac.set_level_track(Some("Stars".to_string()));
+        assert_eq!(muzac.desired_track(), "Stars");
+
+        muzac.set_context(MusicContext::SpecialMode);
+        assert_eq!(muzac.desired_track(), "Echo");
+
+        muzac.set_context(MusicContext::Puzzle);
+        muzac.set_level_track(Some("Not a real track".to_string()));
+        assert!(MusicContext::Puzzle
+            .tracks()
+            .contains(&muzac.desired_track()));
+    }
+}
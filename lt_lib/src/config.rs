@@ -38,10 +38,14 @@ impl Flags {
 pub const DEFAULT_SCREEN_RES: (u32, u32) = (69, 420);
 pub const DEFAULT_DPI: f64 = PI;
 
+///Resource holding the launch flags, and the current config
+///
+///Every change to the config bumps the version, so systems can tell when to recompute things that depend on it
 #[derive(Copy, Clone, Debug)]
 pub struct LTConfig {
     pub flags: Flags,
     pub conf: ParsedConfig,
+    version: usize,
 }
 #[derive(Serialize, Deserialize, Debug)]
 struct ReadInConfig {
@@ -49,13 +53,21 @@ struct ReadInConfig {
     pub dpi_factor: Option<f64>,
     pub maximised: bool,
     pub vol: f32,
+    #[serde(default = "default_sfx_vol")]
+    pub sfx_vol: f32,
 }
-#[derive(Copy, Clone, Debug)]
+fn default_sfx_vol() -> f32 {
+    1.0
+}
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ParsedConfig {
     pub screen_dimensions: (u32, u32),
     pub dpi_factor: f64,
     pub maximised: bool,
+    ///Volume for the music
     pub vol: f32,
+    ///Volume for the sound effects
+    pub sfx_vol: f32,
 }
 impl Default for ParsedConfig {
     fn default() -> Self {
@@ -64,6 +76,7 @@ impl Default for ParsedConfig {
             dpi_factor: DEFAULT_DPI,
             maximised: true,
             vol: 1.0,
+            sfx_vol: default_sfx_vol(),
         }
     }
 }
@@ -74,6 +87,7 @@ impl From<ParsedConfig> for ReadInConfig {
             dpi_factor: Some(c.dpi_factor),
             maximised: c.maximised,
             vol: c.vol,
+            sfx_vol: c.sfx_vol,
         }
     }
 }
@@ -94,6 +108,7 @@ impl ParsedConfig {
                     screen_dimensions: sd,
                     maximised: w.maximised,
                     vol: w.vol,
+                    sfx_vol: w.sfx_vol,
                     dpi_factor,
                 }
             }
@@ -110,12 +125,41 @@ impl ParsedConfig {
     }
 }
 impl LTConfig {
-    pub(crate) fn new() -> Self {
+    ///Parses the launch flags, and reads the config from the config file
+    ///
+    ///Should only be called once at startup, as it reads in the command line arguments
+    pub fn new() -> Self {
         Self {
             flags: Flags::from_args(),
             conf: ParsedConfig::new(),
+            version: 0,
+        }
+    }
+
+    ///Gets the current version of the config - this changes every time the config does
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    ///Sets the config, and bumps the version if anything actually changed
+    pub fn set_conf(&mut self, conf: ParsedConfig) {
+        if self.conf != conf {
+            self.conf = conf;
+            self.version += 1;
         }
     }
+
+    ///Sets the screen dimensions without writing them to the config file - eg. for when the window is resized
+    pub fn set_screen_dimensions(&mut self, screen_dimensions: (u32, u32)) {
+        let mut conf = self.conf;
+        conf.screen_dimensions = screen_dimensions;
+        self.set_conf(conf);
+    }
+
+    ///Reads the config file back in - for use after the settings have been changed
+    pub fn reload(&mut self) {
+        self.set_conf(ParsedConfig::new());
+    }
 }
 impl Default for LTConfig {
     fn default() -> Self {
@@ -1,5 +1,4 @@
 use crate::{
-    config::LTConfig,
     paths::get_directory,
     states_util::{get_levels, levels_len, LevelType},
 };
@@ -101,8 +100,10 @@ impl HighScores {
     }
 
     ///Function to find what the next level to be played is
-    pub fn find_next_level(&self) -> usize {
-        if LTConfig::new().flags.debug {
+    ///
+    ///  - **debug** is whether or not debug options are enabled, in which case all levels are unlocked
+    pub fn find_next_level(&self, debug: bool) -> usize {
+        if debug {
             return levels_len();
         }
 
@@ -0,0 +1,81 @@
+use crate::achievements::{AchievementBackend, LocalAchievements};
+
+///Trait for the services a store platform provides, eg. Steam
+///
+///When the game is built without a platform, or the platform fails to start, `NoPlatform` is used, so everything still works offline
+pub trait PlatformServices: Send + Sync {
+    ///Gets the name of the platform, for logging
+    fn name(&self) -> &'static str;
+    ///Runs any callbacks the platform needs running every frame
+    fn run_callbacks(&mut self);
+    ///Gets the backend to unlock achievements with
+    fn achievement_backend(&self) -> Box<dyn AchievementBackend>;
+}
+
+///Platform which does nothing, and keeps achievements locally
+#[derive(Default, Copy, Clone, Debug)]
+pub struct NoPlatform;
+
+impl PlatformServices for NoPlatform {
+    fn name(&self) -> &'static str {
+        "None"
+    }
+
+    fn run_callbacks(&mut self) {}
+
+    fn achievement_backend(&self) -> Box<dyn AchievementBackend> {
+        Box::new(LocalAchievements::new())
+    }
+}
+
+///Platform for Steam
+#[cfg(feature = "steam")]
+pub struct SteamPlatform(pub steamworks::Client, pub steamworks::SingleClient);
+#[cfg(feature = "steam")]
+unsafe impl Sync for SteamPlatform {}
+#[cfg(feature = "steam")]
+unsafe impl Send for SteamPlatform {}
+
+#[cfg(feature = "steam")]
+impl PlatformServices for SteamPlatform {
+    fn name(&self) -> &'static str {
+        "Steam"
+    }
+
+    fn run_callbacks(&mut self) {
+        self.1.run_callbacks();
+    }
+
+    fn achievement_backend(&self) -> Box<dyn AchievementBackend> {
+        Box::new(crate::achievements::SteamAchievements::new(self.0.clone()))
+    }
+}
+
+///Resource to hold the current platform
+pub struct Platform(pub Box<dyn PlatformServices>);
+
+impl Default for Platform {
+    fn default() -> Self {
+        let p = init_platform();
+        log::info!("Using platform: {}", p.name());
+        Self(p)
+    }
+}
+
+///Starts up Steam if the feature is enabled, falling back to `NoPlatform` if it can't be started
+#[cfg(feature = "steam")]
+pub fn init_platform() -> Box<dyn PlatformServices> {
+    match steamworks::Client::init() {
+        Ok((c, s)) => Box::new(SteamPlatform(c, s)),
+        Err(err) => {
+            log::warn!("Unable to start Steam, carrying on without it: {:?}", err);
+            Box::new(NoPlatform)
+        }
+    }
+}
+
+///Starts up Steam if the feature is enabled, falling back to `NoPlatform` if it can't be started
+#[cfg(not(feature = "steam"))]
+pub fn init_platform() -> Box<dyn PlatformServices> {
+    Box::new(NoPlatform)
+}
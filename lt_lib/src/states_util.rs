@@ -1,4 +1,8 @@
-use crate::{paths::get_directory, CONFIG, HEIGHT, TILE_WIDTH_HEIGHT, WIDTH};
+use crate::{
+    config::{LTConfig, ParsedConfig},
+    paths::get_directory,
+    HEIGHT, TILE_WIDTH_HEIGHT, WIDTH,
+};
 use amethyst::{
     assets::{AssetStorage, Handle, Loader},
     core::{
@@ -9,30 +13,47 @@ use amethyst::{
     ui::{FontAsset, TtfFormat},
 };
 use itertools::Itertools;
-use lazy_static::lazy_static;
 use std::{cmp::Ordering, fs::read_dir, path::Path};
 
 pub const CAMERA_BASE_WIDTH: f32 = (TILE_WIDTH_HEIGHT * WIDTH) as f32; //For ingame-transform Measurements
 pub const CAMERA_BASE_HEIGHT: f32 = (TILE_WIDTH_HEIGHT * HEIGHT) as f32;
 
-lazy_static! {
-    pub static ref CAMERA_WIDTH_MULTIPLIER: (f32, f32) = {
-        let (w, h) = CONFIG.conf.screen_dimensions;
-        let (x, y) = (w as f32 / CAMERA_BASE_WIDTH, h as f32 / CAMERA_BASE_HEIGHT);
+///The size that the UI was originally designed for
+pub const UI_DESIGN_DIMENSIONS: (f32, f32) = (1600.0, 900.0);
 
-        if x > y {
-            (1.0, (y / x) as f32)
-        } else {
-            //WTF?
-            ((x / y) as f32, 1.0)
-        }
-    };
-    pub static ref CAMERA_DIMENSIONS: (f32, f32) = {
-        let (x, y) = *CAMERA_WIDTH_MULTIPLIER;
+///Gets the multiplier for the camera dimensions, so that the whole grid is always visible and keeps its aspect ratio
+///
+///One of the multipliers is always 1.0, and the other is at least 1.0 - the extra space is used for letterboxing/pillarboxing
+pub fn get_camera_width_multiplier(conf: &ParsedConfig) -> (f32, f32) {
+    let (w, h) = conf.screen_dimensions;
+    if w == 0 || h == 0 {
+        return (1.0, 1.0);
+    }
+    let (x, y) = (w as f32 / CAMERA_BASE_WIDTH, h as f32 / CAMERA_BASE_HEIGHT);
+
+    if x > y {
+        //wider than the grid, eg. 21:9 or 32:9 - pillarbox
+        (x / y, 1.0)
+    } else {
+        //taller than the grid, eg. portrait - letterbox
+        (1.0, y / x)
+    }
+}
+
+///Gets the dimensions for the camera, given the current config
+pub fn get_camera_dimensions(conf: &ParsedConfig) -> (f32, f32) {
+    let (x, y) = get_camera_width_multiplier(conf);
 
-        (CAMERA_BASE_WIDTH * x, CAMERA_BASE_HEIGHT * y)
-        //TODO: Ultrawide
-    };
+    (CAMERA_BASE_WIDTH * x, CAMERA_BASE_HEIGHT * y)
+}
+
+///Gets the offset that needs to be added to in-game transforms to centre the grid in the camera
+pub fn get_grid_offset(conf: &ParsedConfig) -> (f32, f32) {
+    let (w, h) = get_camera_dimensions(conf);
+    (
+        (w - CAMERA_BASE_WIDTH) / 2.0,
+        (h - CAMERA_BASE_HEIGHT) / 2.0,
+    )
 }
 
 ///Helper function to initialise a camera in the world
@@ -52,6 +73,18 @@ pub fn init_camera(world: &mut World, wh: (f32, f32)) {
         .build();
 }
 
+///Helper function to point an existing camera at the grid again, for when the camera dimensions change
+///
+///  - **camera** is the camera to change the projection of
+///  - **transform** is the transform of the camera
+///  - **wh** is a tuple containing the new width and height in f32s
+pub fn update_camera(camera: &mut Camera, transform: &mut Transform, wh: (f32, f32)) {
+    let old_z = transform.translation().z;
+    transform.set_translation_xyz(wh.0 * 0.5, wh.1 * 0.5, old_z);
+
+    *camera = Camera::standard_2d(wh.0, wh.1);
+}
+
 ///Helper function to load in a font, given the world, and a path (eg. *ZxSpectrum* (no ttf or path required))
 pub fn load_font(world: &mut World, name: &str) -> Handle<FontAsset> {
     world.read_resource::<Loader>().load(
@@ -83,12 +116,19 @@ pub fn load_sprite_sheet(world: &mut World, path: &str) -> Handle<SpriteSheet> {
     )
 }
 
-pub fn get_scaling_factor() -> (f32, f32) {
-    let c = CONFIG.conf;
-    (
-        c.screen_dimensions.0 as f32 / 1600.0, //game was originally designed for 1600x900
-        c.screen_dimensions.1 as f32 / 900.0,
-    )
+///Gets the scaling factor for UI elements, using the config in the world
+pub fn get_scaling_factor(world: &World) -> (f32, f32) {
+    get_scaling_factor_from_conf(&world.read_resource::<LTConfig>().conf)
+}
+
+///Gets the scaling factor for UI elements from a given config - for use inside systems
+///
+///The same factor is used for both axes, so that the UI always fits inside the screen without stretching, whatever the aspect ratio.
+///UI elements are anchored to the screen edges/middle, so they stay in place on ultrawide or portrait screens
+pub fn get_scaling_factor_from_conf(c: &ParsedConfig) -> (f32, f32) {
+    let (w, h) = c.screen_dimensions;
+    let sf = (w as f32 / UI_DESIGN_DIMENSIONS.0).min(h as f32 / UI_DESIGN_DIMENSIONS.1);
+    (sf, sf)
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -162,3 +202,56 @@ pub fn list_file_names_in_dir<P: AsRef<Path>>(path: P, is_config: bool) -> Vec<S
 
     list
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf_with(screen_dimensions: (u32, u32)) -> ParsedConfig {
+        ParsedConfig {
+            screen_dimensions,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn camera_always_shows_grid_test() {
+        for dims in &[
+            (1600, 900),
+            (2560, 1080),
+            (5120, 1440),
+            (1080, 1920),
+            (1000, 1000),
+        ] {
+            let (w, h) = get_camera_dimensions(&conf_with(*dims));
+            assert!(w >= CAMERA_BASE_WIDTH && h >= CAMERA_BASE_HEIGHT);
+
+            let screen_ratio = dims.0 as f32 / dims.1 as f32;
+            assert!((w / h - screen_ratio).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    pub fn grid_offset_test() {
+        assert_eq!(get_grid_offset(&conf_with((1600, 900))), (0.0, 0.0));
+
+        let (x, y) = get_grid_offset(&conf_with((3200, 900)));
+        assert_eq!((x, y), (CAMERA_BASE_WIDTH / 2.0, 0.0));
+    }
+
+    #[test]
+    pub fn uniform_scaling_test() {
+        assert_eq!(
+            get_scaling_factor_from_conf(&conf_with((1600, 900))),
+            (1.0, 1.0)
+        );
+        assert_eq!(
+            get_scaling_factor_from_conf(&conf_with((5120, 900))),
+            (1.0, 1.0)
+        );
+        assert_eq!(
+            get_scaling_factor_from_conf(&conf_with((800, 1600))),
+            (0.5, 0.5)
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::message_system::MessageList;
+use amethyst::core::{
+    ecs::{Read, System, SystemData, World, Write, WriteExpect},
+    shrev::ReaderId,
+};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    win_related::{GameModeManager, GamePlayingMode},
+};
+use lonely_tribes_lib::{
+    achievements::{Achievement, Achievements},
+    config::LTConfig,
+    platform::Platform,
+};
+
+///System to update the stats, and unlock achievements, from GameEvents
+#[derive(Default)]
+pub struct AchievementSystem {
+    ///ReaderId for the game events
+    reader: Option<ReaderId<GameEvent>>,
+    ///Whether or not a special mode has been used in the current level
+    used_special: bool,
+    ///The procgen seed of the current level, if there is one
+    seed: Option<u32>,
+}
+
+impl<'s> System<'s> for AchievementSystem {
+    type SystemData = (
+        Read<'s, GameEventChannel>,
+        WriteExpect<'s, Achievements>,
+        Write<'s, MessageList>,
+        Read<'s, LTConfig>,
+        Read<'s, GameModeManager>,
+    );
+
+    fn run(&mut self, (events, mut achievements, mut messages, config, gmm): Self::SystemData) {
+        let reader = match &mut self.reader {
+            Some(r) => r,
+            None => return,
+        };
+
+        let events = events.read(reader);
+        //Same as high scores, no achievements with debug options on
+        if config.flags.debug {
+            return;
+        }
+
+        let mut unlocked = Vec::new();
+        let mut changed = false;
+        for event in events {
+            match event {
+                GameEvent::LevelStarted { seed, .. } => {
+                    self.used_special = false;
+                    self.seed = *seed;
+                }
+                GameEvent::ModeChanged { to, .. } => {
+                    if to != &GamePlayingMode::Boring {
+                        self.used_special = true;
+                    }
+                }
+                GameEvent::TribesMerged { .. } => {
+                    achievements.stats.tribes_merged += 1;
+                    changed = true;
+                }
+                GameEvent::LevelWon => {
+                    achievements.stats.levels_won += 1;
+                    if let Some(seed) = self.seed {
+                        achievements.stats.add_seed(seed);
+                    }
+                    //levels without any special moves would give it away for free
+                    if gmm.has_specials()
+                        && !self.used_special
+                        && achievements.unlock(Achievement::NoSpecials)
+                    {
+                        unlocked.push(Achievement::NoSpecials);
+                    }
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if !changed {
+            return;
+        }
+
+        unlocked.append(&mut achievements.check_stats());
+        achievements.save();
+
+        for a in unlocked {
+            messages.push(format!("Achievement Unlocked: {}!", a.name()));
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        let backend = world
+            .entry::<Platform>()
+            .or_insert_with(Platform::default)
+            .0
+            .achievement_backend();
+        world.insert(Achievements::new(backend));
+
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
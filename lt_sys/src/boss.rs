@@ -0,0 +1,211 @@
+use crate::player_overlap_checker::DeleteList;
+use amethyst::{
+    core::{
+        ecs::{
+            Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, World, Write,
+            WriteStorage,
+        },
+        shrev::ReaderId,
+        transform::Transform,
+    },
+    renderer::{palette::Srgba, resources::Tint},
+    ui::UiText,
+};
+use lonely_tribes_animations::{
+    animation::Animator, interpolation::AnimInterpolation, movement::MovementAnimationData,
+    rotation::RotationAnimationData, scale::ScaleAnimationData, tint::TintAnimatorData,
+};
+use lonely_tribes_components::{
+    boss::{Boss, BossHpText, RespawnPoint},
+    colliders::Collider,
+    data_holder::EntityHolder,
+    events::{GameEvent, GameEventChannel},
+    occupancy::OccupancyGrid,
+    player::Player,
+    point_light::PointLight,
+    tile_transform::TileTransform,
+    win_related::{GameState, GameStateEnum},
+};
+use lonely_tribes_tags::trigger_type::TriggerType;
+use std::collections::{HashMap, HashSet};
+
+///Length of the animation for tribe members growing in at a respawn point
+pub const RESPAWN_ANIM_LEN: f32 = 0.3;
+///How many characters wide the boss health bar is
+pub const BOSS_HP_BAR_WIDTH: usize = 20;
+
+///System to run boss fights - a turn passes each time the tribes move
+///
+///Each turn, tribe members on the boss damage it and are used up, the boss might attack, and respawn points might add new tribe members.
+///The level is won when the boss runs out of health, and lost if a tribe with no respawn point has no members left
+#[derive(Default)]
+pub struct BossSystem {
+    ///ReaderId for the game events, to find out when the tribes have moved
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'s> System<'s> for BossSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, TileTransform>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, RespawnPoint>,
+        WriteStorage<'s, Boss>,
+        Read<'s, LazyUpdate>,
+        Write<'s, DeleteList>,
+        Write<'s, EntityHolder>,
+        Write<'s, GameState>,
+        Write<'s, OccupancyGrid>,
+        Write<'s, GameEventChannel>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            tiles,
+            players,
+            respawns,
+            mut bosses,
+            lazy,
+            mut delete_list,
+            mut holder,
+            mut gs,
+            mut grid,
+            mut events,
+        ): Self::SystemData,
+    ) {
+        let moved = match &mut self.reader {
+            Some(reader) => events
+                .read(reader)
+                .any(|ev| matches!(ev, GameEvent::TribeMoved { .. })),
+            None => return,
+        };
+        if !moved || gs.ws != GameStateEnum::ToBeDecided || grid.needs_rebuild() {
+            return;
+        }
+
+        let (boss_tile, boss) = match (&tiles, &mut bosses).join().next() {
+            Some((t, boss)) => (*t, boss),
+            None => return,
+        };
+        boss.turn += 1;
+
+        let mut alive: Vec<(_, usize, TileTransform, u16)> = (&entities, &players, &tiles)
+            .join()
+            .filter(|(e, _, _)| !delete_list.0.contains(e))
+            .map(|(e, p, t)| (e, p.id, *t, p.no_players))
+            .collect();
+        let tribes_before: HashSet<usize> = alive.iter().map(|(_, tribe, _, _)| *tribe).collect();
+
+        //tribe members that walked into the boss
+        for (e, tribe, t, no_players) in alive.iter().filter(|member| member.2 == boss_tile) {
+            let damage = boss.hit(*no_players);
+            delete_list.0.push(*e);
+            grid.remove_player(t, *tribe);
+            events.single_write(GameEvent::BossHit {
+                tribe: *tribe,
+                at: boss_tile,
+                damage,
+                hp_left: boss.hp,
+            });
+        }
+        if boss.is_defeated() {
+            gs.ws = GameStateEnum::End {
+                lost_position: None,
+            };
+            events.single_write(GameEvent::LevelWon);
+            return;
+        }
+        alive.retain(|member| member.2 != boss_tile);
+
+        if boss.attack_due() {
+            let mut targets = boss.attack_targets(alive.len());
+            targets.sort_unstable_by(|a, b| b.cmp(a));
+
+            for i in targets {
+                let (e, tribe, t, _) = alive.swap_remove(i);
+                delete_list.0.push(e);
+                grid.remove_player(&t, tribe);
+                events.single_write(GameEvent::TribeMemberLost { tribe, at: t });
+            }
+        }
+
+        let mut survivors: HashMap<usize, usize> = HashMap::new();
+        for (_, tribe, _, _) in &alive {
+            *survivors.entry(*tribe).or_insert(0) += 1;
+        }
+
+        let mut respawning = HashSet::new();
+        for (point, t) in (&respawns, &tiles).join() {
+            if point.every != 0 {
+                respawning.insert(point.tribe);
+            }
+            if !point.is_due(boss.turn) || grid.is_blocked(t, true) {
+                continue;
+            }
+
+            let mut trans = Transform::default();
+            trans.set_translation_z(0.2);
+            let ent = lazy
+                .create_entity(&entities)
+                .with(point.member_sprite.clone())
+                .with(*t)
+                .with(trans)
+                .with(Collider::new(TriggerType::from_id(&point.tribe)))
+                .with(Player::new(point.tribe))
+                .with(Animator::<MovementAnimationData>::default())
+                .with(Animator::<RotationAnimationData>::default())
+                .with(Animator::new(ScaleAnimationData::new(
+                    RESPAWN_ANIM_LEN,
+                    AnimInterpolation::ReverseExponential,
+                )))
+                .with(Animator::<TintAnimatorData>::default())
+                .with(PointLight::for_tribe(3, point.tribe))
+                .with(Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)))
+                .build();
+            //so that it gets hidden when pausing, like the rest of the tribe members
+            holder.add_player_entity(ent);
+            grid.add_player(t, point.tribe);
+            *survivors.entry(point.tribe).or_insert(0) += 1;
+            events.single_write(GameEvent::TribeRespawned {
+                tribe: point.tribe,
+                at: *t,
+            });
+        }
+
+        let wiped_out = tribes_before
+            .iter()
+            .any(|tribe| !survivors.contains_key(tribe) && !respawning.contains(tribe));
+        if wiped_out {
+            gs.ws = GameStateEnum::End {
+                lost_position: Some(boss_tile),
+            };
+            events.single_write(GameEvent::LevelLost { at: boss_tile });
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
+
+///System to update the boss health bar
+pub struct BossHpUiSystem;
+
+impl<'s> System<'s> for BossHpUiSystem {
+    type SystemData = (
+        ReadStorage<'s, Boss>,
+        ReadStorage<'s, BossHpText>,
+        WriteStorage<'s, UiText>,
+    );
+
+    fn run(&mut self, (bosses, hp_texts, mut texts): Self::SystemData) {
+        if let Some(boss) = bosses.join().next() {
+            for (_, text) in (&hp_texts, &mut texts).join() {
+                text.text = boss.hp_bar(BOSS_HP_BAR_WIDTH);
+            }
+        }
+    }
+}
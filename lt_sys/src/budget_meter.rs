@@ -0,0 +1,58 @@
+use amethyst::{
+    core::{
+        ecs::{Join, Read, ReadStorage, System, WriteStorage},
+        Time,
+    },
+    renderer::resources::Tint,
+    ui::UiTransform,
+};
+use lonely_tribes_components::{
+    budget_meter::{BudgetMeter, ModeFx},
+    screen_scaled::ScreenScaled,
+    win_related::GameModeManager,
+};
+use lonely_tribes_lib::{config::LTConfig, states_util::get_scaling_factor_from_conf};
+
+///System to fade the special mode effects as the special moves get used up
+///
+///Runs after the TintAnimatorSystem, so the lighting can be faded on top of
+pub struct ModeFxSystem;
+
+impl<'s> System<'s> for ModeFxSystem {
+    type SystemData = (
+        Read<'s, GameModeManager>,
+        WriteStorage<'s, ModeFx>,
+        WriteStorage<'s, Tint>,
+    );
+
+    fn run(&mut self, (gmm, mut fxs, mut tints): Self::SystemData) {
+        let fraction = gmm.fraction_left();
+        for (fx, tint) in (&mut fxs, &mut tints).join() {
+            tint.0.alpha = fx.fade(tint.0.alpha, fraction);
+        }
+    }
+}
+
+///System to animate the HUD bar for the special moves left
+pub struct BudgetMeterSystem;
+
+impl<'s> System<'s> for BudgetMeterSystem {
+    type SystemData = (
+        Read<'s, GameModeManager>,
+        Read<'s, Time>,
+        Read<'s, LTConfig>,
+        WriteStorage<'s, BudgetMeter>,
+        ReadStorage<'s, ScreenScaled>,
+        WriteStorage<'s, UiTransform>,
+    );
+
+    fn run(&mut self, (gmm, time, config, mut meters, scaleds, mut trans): Self::SystemData) {
+        let fraction = gmm.fraction_left();
+        let (sf_x, _) = get_scaling_factor_from_conf(&config.conf);
+
+        for (meter, scaled, trans) in (&mut meters, &scaleds, &mut trans).join() {
+            let shown = meter.step(fraction, time.delta_seconds());
+            trans.width = scaled.width * sf_x * shown;
+        }
+    }
+}
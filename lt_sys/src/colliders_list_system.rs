@@ -1,11 +1,16 @@
 use amethyst::core::ecs::{Join, ReadStorage, System, Write};
 use lonely_tribes_components::{
-    colliders::{Collider, ColliderList},
+    colliders::{Collider, Pushable},
+    floors::Floor,
+    occupancy::OccupancyGrid,
     player::Player,
     tile_transform::TileTransform,
 };
+use lonely_tribes_tags::trigger_type::TriggerType;
 
-///System to update ColliderList, and LevelState
+///System to rebuild the OccupancyGrid from the components, when it has been invalidated
+///
+///Moving entities update the grid themselves, so this only needs to happen when a level gets loaded
 pub struct ListSystem;
 
 impl<'s> System<'s> for ListSystem {
@@ -13,28 +18,32 @@ impl<'s> System<'s> for ListSystem {
         ReadStorage<'s, TileTransform>,
         ReadStorage<'s, Collider>,
         ReadStorage<'s, Player>,
-        Write<'s, ColliderList>,
+        ReadStorage<'s, Pushable>,
+        ReadStorage<'s, Floor>,
+        Write<'s, OccupancyGrid>,
     );
 
-    fn run(&mut self, (tiles, colliders, players, mut list): Self::SystemData) {
-        let mut colliders_list = Vec::new();
-        let mut triggers_list = Vec::new();
-        let mut player_list = Vec::new();
+    fn run(&mut self, (tiles, colliders, players, pushables, floors, mut grid): Self::SystemData) {
+        if !grid.needs_rebuild() {
+            return;
+        }
 
-        for (t, c) in (&tiles, &colliders).join() {
-            let tt = *t;
-            if let Some(t) = c.trigger {
-                triggers_list.push((tt, t));
-            } else {
-                colliders_list.push(tt);
+        let mut nu_grid = OccupancyGrid::empty();
+        for (t, c, pushable) in (&tiles, &colliders, pushables.maybe()).join() {
+            match c.trigger {
+                None if pushable.is_some() => nu_grid.add_pushable(t),
+                Some(TriggerType::Player(_)) => {}
+                Some(trigger) => nu_grid.add_trigger(t, trigger),
+                None => nu_grid.add_collider(t),
             }
         }
+        for (t, f) in (&tiles, &floors).join() {
+            nu_grid.add_floor(t, f.0);
+        }
+        for (t, p) in (&tiles, &players).join() {
+            nu_grid.add_player(t, p.id);
+        }
 
-        (&tiles, &players)
-            .join()
-            .for_each(|(t, p)| player_list.push((*p, *t)));
-
-        list.set(colliders_list);
-        list.set_triggers(triggers_list);
+        *grid = nu_grid;
     }
 }
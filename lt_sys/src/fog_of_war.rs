@@ -1,65 +1,180 @@
 use crate::move_player::HELD_INTERVAL;
 use amethyst::{
-    core::ecs::{Join, Read, ReadStorage, System, Write, WriteStorage},
+    core::{
+        ecs::{
+            hibitset::BitSet, storage::ComponentEvent, world::Index, Entities, Join, Read,
+            ReadStorage, System, SystemData, World, Write, WriteStorage,
+        },
+        shrev::ReaderId,
+        Time,
+    },
     renderer::resources::Tint,
 };
 use lonely_tribes_animations::{
     animation::Animator, interpolation::AnimInterpolation, tint::TintAnimatorData,
 };
 use lonely_tribes_components::{
-    colliders::ColliderList,
+    occupancy::{OccupancyGrid, TileGrid},
     point_light::{PointLight, TintOverride},
     tile_transform::TileTransform,
 };
-use lonely_tribes_fog_of_war::{fog::LightCacher, light_list::LightList};
+use lonely_tribes_fog_of_war::{
+    explored::ExploredTiles,
+    fog::{LightCacher, TileLight},
+    light_list::LightList,
+};
+use lonely_tribes_lib::config::LTConfig;
+use std::collections::HashMap;
 
 #[derive(Default)]
 pub struct FogOfWarSystem {
     cacher: LightCacher,
+    ///The lighting each entity was last animated towards, so entities only get new animator data when it changes
+    applied: HashMap<Index, TileLight>,
+    ///The tile each entity was on when it was last updated
+    placed: HashMap<Index, TileTransform>,
+    ///Every entity on each tile, so only the entities on tiles whose lighting changed get updated
+    by_tile: TileGrid<Vec<Index>>,
+    ///ReaderId for changes to TileTransforms
+    reader: Option<ReaderId<ComponentEvent>>,
+}
+
+impl FogOfWarSystem {
+    ///Takes an entity off the tile it was on
+    fn unplace(&mut self, id: Index) {
+        if let Some(old) = self.placed.remove(&id) {
+            if let Some(on_tile) = self.by_tile.get_mut(&old) {
+                on_tile.retain(|e| *e != id);
+            }
+        }
+    }
 }
 
 pub const TINT_ANIMATION_TIME: f32 = HELD_INTERVAL;
 
+///Gets the colour of the light currently on a tile from its tint, so colour changes can be animated from it
+///
+///  - **tint** is the current tint of the tile
+///  - **fallback** is the colour to use if the tile is currently fully dark
+fn current_colour(tint: &Tint, fallback: [f32; 3]) -> [f32; 3] {
+    let a = tint.0.alpha;
+    if a > 0.0 {
+        [tint.0.red / a, tint.0.green / a, tint.0.blue / a]
+    } else {
+        fallback
+    }
+}
+
 impl<'s> System<'s> for FogOfWarSystem {
     type SystemData = (
+        Entities<'s>,
         ReadStorage<'s, TileTransform>,
         ReadStorage<'s, Tint>,
         Read<'s, LightList>,
-        Read<'s, ColliderList>,
+        Read<'s, OccupancyGrid>,
         ReadStorage<'s, TintOverride>,
         WriteStorage<'s, Animator<TintAnimatorData>>,
+        Read<'s, LTConfig>,
+        Write<'s, ExploredTiles>,
     );
 
     fn run(
         &mut self,
-        (tiles, tints, lights, collider_list, overrides, mut animators): Self::SystemData,
+        (
+            entities,
+            tiles,
+            tints,
+            lights,
+            occupancy,
+            overrides,
+            mut animators,
+            config,
+            mut explored,
+        ): Self::SystemData,
     ) {
-        let lighted_cells = self
-            .cacher
-            .get_lighted_cells(lights.get(), collider_list.get());
+        //entities which have moved, or are new, need updating wherever they are
+        let mut moved = BitSet::new();
+        if let Some(reader) = &mut self.reader {
+            for event in tiles.channel().read(reader) {
+                match event {
+                    ComponentEvent::Inserted(id) | ComponentEvent::Modified(id) => {
+                        moved.add(*id);
+                    }
+                    ComponentEvent::Removed(id) => {
+                        moved.remove(*id);
+                        self.unplace(*id);
+                        self.applied.remove(id);
+                    }
+                }
+            }
+        }
 
-        for (tile, tint, anim) in (&tiles, &tints, &mut animators).join() {
-            let factor = *lighted_cells.get(tile).unwrap_or(&0.0);
-            anim.replace_data(TintAnimatorData::new(
-                tint.0.alpha,
-                factor,
-                None,
-                TINT_ANIMATION_TIME,
-                AnimInterpolation::Linear,
-            ));
+        let mut dirty = BitSet::new();
+        for (ent, tile, _) in (&entities, &tiles, &moved).join() {
+            if self.placed.get(&ent.id()) == Some(tile) {
+                continue;
+            }
+            self.unplace(ent.id());
+            if let Some(on_tile) = self.by_tile.get_mut(tile) {
+                on_tile.push(ent.id());
+            }
+            self.placed.insert(ent.id(), *tile);
+            dirty.add(ent.id());
+        }
+
+        //and everything on a tile whose lighting has changed
+        let changed = self.cacher.update(
+            lights.get(),
+            occupancy.colliders(),
+            config.flags.fow_enabled(),
+        );
+        for tile in &changed {
+            if let Some(on_tile) = self.by_tile.get(tile) {
+                for id in on_tile {
+                    dirty.add(*id);
+                }
+            }
         }
 
-        for (tile, tint, t_override, anim) in (&tiles, &tints, &overrides, &mut animators).join() {
-            let factor = *lighted_cells.get(tile).unwrap_or(&0.0);
-            anim.replace_data(TintAnimatorData::new(
+        for (ent, tile, tint, anim, t_override, _) in (
+            &entities,
+            &tiles,
+            &tints,
+            &mut animators,
+            overrides.maybe(),
+            &dirty,
+        )
+            .join()
+        {
+            let light = self.cacher.get(tile);
+            let target = TileLight {
+                factor: explored.factor(tile, light.factor),
+                ..light
+            };
+            if self.applied.get(&ent.id()) == Some(&target) {
+                continue;
+            }
+            self.applied.insert(ent.id(), target);
+
+            let data = TintAnimatorData::new(
                 tint.0.alpha,
-                factor,
-                Some(t_override.0),
+                target.factor,
+                t_override.map(|t| t.0),
                 TINT_ANIMATION_TIME,
                 AnimInterpolation::Linear,
-            ));
+            );
+            anim.replace_data(if t_override.is_some() {
+                data
+            } else {
+                data.with_colours(current_colour(tint, target.colour), target.colour)
+            });
         }
     }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.write_storage::<TileTransform>().register_reader());
+    }
 }
 
 pub struct LightListSystem;
@@ -69,12 +184,14 @@ impl<'s> System<'s> for LightListSystem {
         ReadStorage<'s, TileTransform>,
         ReadStorage<'s, PointLight>,
         Write<'s, LightList>,
+        Read<'s, Time>,
     );
 
-    fn run(&mut self, (tiles, lights, mut light_list): Self::SystemData) {
+    fn run(&mut self, (tiles, lights, mut light_list, time): Self::SystemData) {
+        let now = time.absolute_time_seconds() as f32;
         let mut list = Vec::new();
         for (t, p) in (&tiles, &lights).join() {
-            list.push((*t, *p));
+            list.push((*t, p.flickered(*t, now)));
         }
         light_list.set(list);
     }
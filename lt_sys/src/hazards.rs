@@ -0,0 +1,117 @@
+use crate::player_overlap_checker::DeleteList;
+use amethyst::{
+    core::ecs::{Entities, Join, ReadStorage, System, Write, WriteStorage},
+    renderer::SpriteRender,
+};
+use lonely_tribes_components::{
+    boss::RespawnPoint,
+    events::{GameEvent, GameEventChannel},
+    hazards::Hazard,
+    npcs::Npc,
+    occupancy::OccupancyGrid,
+    player::Player,
+    tile_transform::TileTransform,
+    win_related::{GameState, GameStateEnum},
+};
+use std::collections::{HashMap, HashSet};
+
+///System to toggle timed hazards, and get rid of any tribe members that walk into active hazards or touch NPCs
+///
+///If a tribe without a respawn point loses every member, or a member walks onto a hazard which loses the level, then the level is lost
+pub struct HazardSystem;
+
+impl<'s> System<'s> for HazardSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, TileTransform>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Npc>,
+        ReadStorage<'s, RespawnPoint>,
+        WriteStorage<'s, Hazard>,
+        WriteStorage<'s, SpriteRender>,
+        Write<'s, DeleteList>,
+        Write<'s, GameState>,
+        Write<'s, OccupancyGrid>,
+        Write<'s, GameEventChannel>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            tiles,
+            players,
+            npcs,
+            respawns,
+            mut hazards,
+            mut sprites,
+            mut delete_list,
+            mut gs,
+            mut grid,
+            mut events,
+        ): Self::SystemData,
+    ) {
+        if gs.ws != GameStateEnum::ToBeDecided {
+            return;
+        }
+
+        //whether or not each deadly tile loses the level straight away
+        let mut active: HashMap<TileTransform, bool> = HashMap::new();
+        for (t, hazard, spr) in (&tiles, &mut hazards, &mut sprites).join() {
+            if hazard.update(gs.level_no_of_moves) {
+                spr.sprite_number = hazard.sprite();
+            }
+            if hazard.active {
+                active.insert(*t, hazard.kind.loses_level());
+            }
+        }
+        for (t, _) in (&tiles, &npcs).join() {
+            active.entry(*t).or_insert(false);
+        }
+        if active.is_empty() {
+            return;
+        }
+
+        let respawning: HashSet<usize> = respawns
+            .join()
+            .filter(|point| point.every != 0)
+            .map(|point| point.tribe)
+            .collect();
+        let mut survivors: HashMap<usize, usize> = HashMap::new();
+        let mut lost_members = Vec::new();
+        let mut lost_at = None;
+        for (e, p, t) in (&entities, &players, &tiles).join() {
+            if delete_list.0.contains(&e) {
+                continue;
+            }
+
+            let count = survivors.entry(p.id).or_insert(0);
+            match active.get(t) {
+                Some(true) => {
+                    lost_at.get_or_insert(*t);
+                    *count += 1;
+                }
+                Some(false) => lost_members.push((e, p.id, *t)),
+                None => *count += 1,
+            }
+        }
+
+        for (e, tribe, t) in lost_members {
+            delete_list.0.push(e);
+            grid.remove_player(&t, tribe);
+            events.single_write(GameEvent::TribeMemberLost { tribe, at: t });
+
+            //a tribe with no members left can never be merged, unless more members are on the way
+            if survivors.get(&tribe) == Some(&0) && !respawning.contains(&tribe) {
+                lost_at.get_or_insert(t);
+            }
+        }
+
+        if let Some(at) = lost_at {
+            gs.ws = GameStateEnum::End {
+                lost_position: Some(at),
+            };
+            events.single_write(GameEvent::LevelLost { at });
+        }
+    }
+}
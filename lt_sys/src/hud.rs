@@ -0,0 +1,43 @@
+use amethyst::{
+    core::ecs::{Join, Read, ReadStorage, System, WriteStorage},
+    ui::UiText,
+};
+use lonely_tribes_components::{
+    hud::{mode_icon, HudElement, HudText, HudValues},
+    player::{Player, TribeControl},
+    win_related::{GameModeManager, GameState},
+};
+
+///System to update the HUD in the PuzzleState
+///
+///Text only gets remade when the values it shows have changed
+pub struct HudSystem;
+
+impl<'s> System<'s> for HudSystem {
+    type SystemData = (
+        Read<'s, GameState>,
+        Read<'s, GameModeManager>,
+        Read<'s, TribeControl>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, HudText>,
+        WriteStorage<'s, UiText>,
+    );
+
+    fn run(&mut self, (gws, gmm, control, players, mut huds, mut texts): Self::SystemData) {
+        let values = HudValues::new(gws.level_no_of_moves, &gmm, &control, players.join());
+
+        for (hud, text) in (&mut huds, &mut texts).join() {
+            if hud.shown.as_ref() == Some(&values) {
+                continue;
+            }
+
+            if let Some(nu_text) = values.text(hud.element, &gmm) {
+                text.text = nu_text;
+            }
+            if hud.element == HudElement::ModeIcon {
+                text.color = mode_icon(gmm.modes.get(values.mode)).1;
+            }
+            hud.shown = Some(values.clone());
+        }
+    }
+}
@@ -1,12 +1,23 @@
+pub mod achievements;
+pub mod boss;
+pub mod budget_meter;
 pub mod colliders_list_system;
 pub mod fog_of_war;
 pub mod fps_counter;
+pub mod hazards;
+pub mod hud;
 pub mod message_system;
 pub mod move_player;
+pub mod music_director;
+pub mod npcs;
+pub mod platform_manager;
 pub mod player_overlap_checker;
-pub mod steamworks_manager;
+pub mod screen_scaling;
+pub mod script;
+pub mod sfx;
+pub mod switches;
 pub mod tint_animator;
+pub mod tribe_selector;
 pub mod txt_wobble_system;
 pub mod uitext_animator;
-pub mod update_score;
 pub mod update_tile_transforms;
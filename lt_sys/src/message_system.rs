@@ -8,7 +8,14 @@ use amethyst::{
 use lonely_tribes_animations::{
     animation::Animator, interpolation::AnimInterpolation, tint::TintAnimatorData,
 };
-use lonely_tribes_lib::states_util::{get_scaling_factor, load_font};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    screen_scaled::ScreenScaled,
+};
+use lonely_tribes_lib::{
+    config::LTConfig,
+    states_util::{get_scaling_factor_from_conf, load_font},
+};
 use std::{
     borrow::BorrowMut,
     collections::VecDeque,
@@ -59,7 +66,8 @@ impl Iterator for TimedMessagesToAdd {
     }
 }
 
-const MESSAGE_PER_LETTER: f32 = 0.1;
+///How long each letter of a message stays on screen for, in seconds
+pub const MESSAGE_PER_LETTER: f32 = 0.1;
 
 #[derive(Default)]
 pub struct MessageSystem {
@@ -68,20 +76,18 @@ pub struct MessageSystem {
     font: Option<FontHandle>,
 }
 
-lazy_static::lazy_static! {
-    pub static ref DEFAULT_UI_TRANS: UiTransform = {
-        let (sfx, sfy) = get_scaling_factor();
-        UiTransform::new(
-            "message_system".to_string(),
-            Anchor::TopRight,
-            Anchor::TopRight,
-            -90.0 * sfx,
-            -90.0 * sfy,
-            0.5,
-            333.3 * sfx,
-            500.0 * sfy
-        )
-    };
+///Gets the UiTransform for messages, given the scaling factor
+pub fn get_default_ui_trans((sfx, sfy): (f32, f32)) -> UiTransform {
+    UiTransform::new(
+        "message_system".to_string(),
+        Anchor::TopRight,
+        Anchor::TopRight,
+        -90.0 * sfx,
+        -90.0 * sfy,
+        0.5,
+        333.3 * sfx,
+        500.0 * sfy,
+    )
 }
 
 impl<'s> System<'s> for MessageSystem {
@@ -93,11 +99,25 @@ impl<'s> System<'s> for MessageSystem {
         WriteStorage<'s, Animator<TintAnimatorData>>,
         Read<'s, Time>,
         Write<'s, TimedMessagesToAdd>,
+        Read<'s, LTConfig>,
+        WriteStorage<'s, ScreenScaled>,
+        Write<'s, GameEventChannel>,
     );
 
     fn run(
         &mut self,
-        (entites, mut message_list, mut transforms, mut txts, mut aniamtors, time, mut timed_msgs): Self::SystemData,
+        (
+            entites,
+            mut message_list,
+            mut transforms,
+            mut txts,
+            mut aniamtors,
+            time,
+            mut timed_msgs,
+            config,
+            mut scaleds,
+            mut events,
+        ): Self::SystemData,
     ) {
         for msg in std::mem::take(&mut message_list.0) {
             self.queue.push_back(msg);
@@ -128,18 +148,22 @@ impl<'s> System<'s> for MessageSystem {
             };
 
             if let Some(msg) = msg {
+                events.single_write(GameEvent::MessagePosted(msg.clone()));
+
                 if let Some(handle) = self.font.clone() {
                     let time = MESSAGE_PER_LETTER * msg.len() as f32;
 
-                    let (sfx, _sfy) = get_scaling_factor();
+                    let sf = get_scaling_factor_from_conf(&config.conf);
                     let txt = UiText::new(
                         handle,
                         msg,
                         [1.0; 4],
-                        25.0 * sfx,
+                        25.0 * sf.1,
                         LineMode::Wrap,
                         Anchor::TopRight,
                     );
+                    let trans = get_default_ui_trans(sf);
+                    let scaled = ScreenScaled::new(&trans, Some(txt.font_size), sf);
 
                     let anim = Animator::new(TintAnimatorData::new(
                         1.0,
@@ -151,9 +175,10 @@ impl<'s> System<'s> for MessageSystem {
 
                     let ent = entites
                         .build_entity()
-                        .with(DEFAULT_UI_TRANS.clone(), &mut transforms)
+                        .with(trans, &mut transforms)
                         .with(txt, &mut txts)
                         .with(anim, &mut aniamtors)
+                        .with(scaled, &mut scaleds)
                         .build();
 
                     self.current = Some((time, ent));
@@ -1,22 +1,26 @@
 use amethyst::{
     core::{
-        ecs::{Join, Read, ReadStorage, System, Write, WriteStorage},
+        ecs::{Entities, Join, Read, ReadStorage, System, Write, WriteStorage},
         Time,
     },
     input::{InputHandler, StringBindings, VirtualKeyCode},
 };
 use lonely_tribes_animations::{
     animation::Animator, interpolation::AnimInterpolation, movement::MovementAnimationData,
-    rotation::RotationAnimationData,
+    rotation::RotationAnimationData, scale::ScaleAnimationData,
 };
 use lonely_tribes_components::{
-    colliders::ColliderList,
-    player::Player,
+    colliders::Pushable,
+    events::{GameEvent, GameEventChannel},
+    floors::FloorEffect,
+    occupancy::OccupancyGrid,
+    player::{Player, TribeControl},
+    special_modes::{swap_pairs, SpecialMode},
     tile_transform::TileTransform,
-    win_related::{GameModeManager, GamePlayingMode, GameState},
+    win_related::{GameModeManager, GameState, GameStateEnum},
 };
-use lonely_tribes_lib::{CONFIG, HEIGHT, WIDTH};
-use rand::Rng;
+use lonely_tribes_lib::config::Flags;
+use std::collections::HashSet;
 
 ///Resource to optionally disable movement - unless it is true, we assume false as the default is false
 pub struct MovementDisabler {
@@ -30,6 +34,12 @@ impl Default for MovementDisabler {
 
 pub const PLAYER_MOVEMENT_ANIM_LEN: f32 = 0.125;
 pub const HELD_INTERVAL: f32 = 0.05;
+///How long it takes for a tribe member to reappear after teleporting
+pub const TELEPORT_ANIM_LEN: f32 = 0.3;
+///How long it takes for a one-way tile to move a tribe member along
+pub const ONE_WAY_ANIM_LEN: f32 = 0.25;
+///How long it takes for tribe members to swap places in a swapping mode
+pub const SWAP_ANIM_LEN: f32 = 0.4;
 
 ///System for capturing player movement, and collision
 #[derive(Default)]
@@ -51,10 +61,9 @@ pub struct MovementType {
     pub movement_timer: Option<f32>,
 }
 
-impl Default for MovementType {
-    fn default() -> Self {
-        let opts = CONFIG.flags;
-
+impl MovementType {
+    ///Constructor which uses the launch flags to work out whether to use held or stepped movement
+    pub fn new(opts: Flags) -> Self {
         if opts.timed_movement {
             Self {
                 can_move: None,
@@ -68,54 +77,72 @@ impl Default for MovementType {
         }
     }
 }
+impl Default for MovementType {
+    fn default() -> Self {
+        Self {
+            can_move: Some(false),
+            movement_timer: None,
+        }
+    }
+}
 
 impl<'s> System<'s> for MovePlayerSystem {
     type SystemData = (
+        Entities<'s>,
         WriteStorage<'s, TileTransform>,
         ReadStorage<'s, Player>,
+        ReadStorage<'s, Pushable>,
         Read<'s, InputHandler<StringBindings>>,
-        Read<'s, ColliderList>,
+        Write<'s, OccupancyGrid>,
         Read<'s, Time>,
         Write<'s, GameState>,
         Read<'s, MovementDisabler>,
         WriteStorage<'s, Animator<MovementAnimationData>>,
         WriteStorage<'s, Animator<RotationAnimationData>>,
+        WriteStorage<'s, Animator<ScaleAnimationData>>,
         Write<'s, MovementType>,
         Write<'s, GameModeManager>,
+        Write<'s, GameEventChannel>,
+        Write<'s, TribeControl>,
     );
 
     fn run(
         &mut self,
         (
+            entities,
             mut tiles,
             players,
+            pushables,
             input,
-            list,
+            mut grid,
             time,
             mut gws,
             movement_disabler,
             mut movement_animators,
             mut rotation_animators,
+            mut scale_animators,
             mut movement,
             mut gm,
+            mut events,
+            mut control,
         ): Self::SystemData,
     ) {
-        let mut add_to_score = false;
-        let mode = gm.current_mode;
-
-        #[allow(unused_variables)]
-        let (collision_tiles, trigger_tiles) = {
-            let mut collisions = list.get().clone();
-            let mut triggers = list.get_triggers().clone();
-
-            if mode == GamePlayingMode::AllTheColliders {
-                std::mem::take(&mut triggers)
-                    .into_iter()
-                    .for_each(|(tt, _)| collisions.push(tt));
-            }
+        //once the level is over (eg. while the level script's outro plays), moves shouldn't count towards the score or use up specials
+        if gws.ws != GameStateEnum::ToBeDecided {
+            return;
+        }
 
-            (collisions, triggers)
-        };
+        let mut add_to_score = false;
+        let mut box_moves = Vec::new();
+        let mut moved = Vec::new();
+        //the grid only gets updated after every tribe member has picked where to go, so the join order doesn't change what is blocked
+        let mut player_moves = Vec::new();
+        //tiles which are being moved onto this frame, so that blocks can't get pushed onto the same tile as something else
+        let mut claimed = ClaimedTiles::default();
+        let mut tribes_moved = HashSet::new();
+        let mode = gm.modes.get(gm.current_mode);
+        let swapping = mode.swaps_tribes();
+        let mut swap_now = false;
 
         let (proposed_tile_addition, actual_movement) = {
             let mut t = TileTransform::default();
@@ -134,40 +161,27 @@ impl<'s> System<'s> for MovePlayerSystem {
                 movement = false;
             }
 
-            if mode == GamePlayingMode::Frenzy {
-                t *= 3;
-            }
-
-            (t, movement)
+            (mode.transform_input(t), movement)
         };
 
-        let proposed_tile_closure = |tile: TileTransform, base_len: f32| {
-            let mut rng = rand::thread_rng();
-            let mut anim_len = base_len;
-            let mut interp = AnimInterpolation::Linear;
-
-            let t = match mode {
-                GamePlayingMode::TradeOff => {
-                    tile + TileTransform::new(rng.gen_range(-1..=1), rng.gen_range(-1..=1))
-                }
-                GamePlayingMode::Crazy => {
-                    anim_len *= 3.0;
-                    interp = AnimInterpolation::ReverseExponential;
-                    TileTransform::new(
-                        rng.gen_range(0..WIDTH as i32),
-                        rng.gen_range(0..HEIGHT as i32),
-                    )
-                }
-                _ => tile + proposed_tile_addition,
+        let proposed_tile_closure = |tile: TileTransform, tribe: usize, base_len: f32| {
+            let interp = if mode.smooth_anim() {
+                AnimInterpolation::ReverseExponential
+            } else {
+                AnimInterpolation::Linear
             };
-            (t, anim_len, interp)
+            let t = mode.propose_move(tile, proposed_tile_addition, tribe, &mut rand::thread_rng());
+            (t, mode.anim_len(base_len), interp)
         };
 
         if let Some(timer) = &mut movement.movement_timer {
             *timer += time.delta_seconds();
 
             if *timer > HELD_INTERVAL && !movement_disabler.enabled {
-                for (tile, _, movement_anim, rot_anim) in (
+                swap_now = swapping && actual_movement;
+
+                for (e, tile, player, movement_anim, rot_anim) in (
+                    &entities,
                     &mut tiles,
                     &players,
                     &mut movement_animators,
@@ -175,15 +189,33 @@ impl<'s> System<'s> for MovePlayerSystem {
                 )
                     .join()
                 {
+                    if swapping || !control.can_move(player.id) {
+                        continue;
+                    }
+
                     let (proposed_tile, anim_len, interp) =
-                        proposed_tile_closure(*tile, PLAYER_MOVEMENT_ANIM_LEN);
-                    let works = if mode == GamePlayingMode::Nudger {
-                        tile_works(proposed_tile, &[])
-                    } else {
-                        tile_works(proposed_tile, &collision_tiles)
+                        proposed_tile_closure(*tile, player.id, PLAYER_MOVEMENT_ANIM_LEN);
+                    let push = get_push(*tile, proposed_tile, &grid, mode, &claimed);
+                    let works = match push {
+                        Push::Moves(_, _) => true,
+                        Push::Blocked => false,
+                        Push::Clear => {
+                            mode.tile_works(proposed_tile, &grid)
+                                && claimed.free_for_tribe(&proposed_tile)
+                        }
                     } && &proposed_tile != tile;
 
                     if works && actual_movement {
+                        let from = *tile;
+                        if let Push::Moves(box_from, box_to) = push {
+                            claimed.blocks.insert(box_to);
+                            box_moves.push((box_from, box_to, anim_len, interp));
+                            events.single_write(GameEvent::BoxPushed {
+                                tribe: player.id,
+                                from: box_from,
+                                to: box_to,
+                            });
+                        }
                         set_tiletransform_with_anim(
                             tile,
                             proposed_tile,
@@ -192,9 +224,23 @@ impl<'s> System<'s> for MovePlayerSystem {
                             anim_len,
                             interp,
                         );
+                        claimed.tribes.insert(proposed_tile);
+                        player_moves.push((from, proposed_tile, player.id));
+                        moved.push(e);
+                        tribes_moved.insert(player.id);
+                        events.single_write(GameEvent::TribeMoved {
+                            tribe: player.id,
+                            from,
+                            to: proposed_tile,
+                        });
                         if mode.adds_to_score() {
                             add_to_score = true;
                         }
+                    } else if actual_movement {
+                        events.single_write(GameEvent::TribeBumped {
+                            tribe: player.id,
+                            at: *tile,
+                        });
                     }
                 }
 
@@ -204,7 +250,10 @@ impl<'s> System<'s> for MovePlayerSystem {
 
         if let Some(can_move) = movement.can_move {
             if !movement_disabler.enabled {
-                for (tile, _, movement_anim, rot_anim) in (
+                swap_now = swapping && can_move && actual_movement;
+
+                for (e, tile, player, movement_anim, rot_anim) in (
+                    &entities,
                     &mut tiles,
                     &players,
                     &mut movement_animators,
@@ -212,15 +261,33 @@ impl<'s> System<'s> for MovePlayerSystem {
                 )
                     .join()
                 {
+                    if swapping || !control.can_move(player.id) {
+                        continue;
+                    }
+
                     let (proposed_tile, anim_len, interp) =
-                        proposed_tile_closure(*tile, PLAYER_MOVEMENT_ANIM_LEN);
-                    let works = if mode == GamePlayingMode::Nudger {
-                        tile_works(proposed_tile, &[])
-                    } else {
-                        tile_works(proposed_tile, &collision_tiles)
+                        proposed_tile_closure(*tile, player.id, PLAYER_MOVEMENT_ANIM_LEN);
+                    let push = get_push(*tile, proposed_tile, &grid, mode, &claimed);
+                    let works = match push {
+                        Push::Moves(_, _) => true,
+                        Push::Blocked => false,
+                        Push::Clear => {
+                            mode.tile_works(proposed_tile, &grid)
+                                && claimed.free_for_tribe(&proposed_tile)
+                        }
                     } && &proposed_tile != tile;
 
                     if works && can_move && actual_movement {
+                        let from = *tile;
+                        if let Push::Moves(box_from, box_to) = push {
+                            claimed.blocks.insert(box_to);
+                            box_moves.push((box_from, box_to, anim_len, interp));
+                            events.single_write(GameEvent::BoxPushed {
+                                tribe: player.id,
+                                from: box_from,
+                                to: box_to,
+                            });
+                        }
                         set_tiletransform_with_anim(
                             tile,
                             proposed_tile,
@@ -229,7 +296,21 @@ impl<'s> System<'s> for MovePlayerSystem {
                             anim_len,
                             interp,
                         );
+                        claimed.tribes.insert(proposed_tile);
+                        player_moves.push((from, proposed_tile, player.id));
+                        moved.push(e);
+                        tribes_moved.insert(player.id);
+                        events.single_write(GameEvent::TribeMoved {
+                            tribe: player.id,
+                            from,
+                            to: proposed_tile,
+                        });
                         add_to_score = true;
+                    } else if can_move && actual_movement {
+                        events.single_write(GameEvent::TribeBumped {
+                            tribe: player.id,
+                            at: *tile,
+                        });
                     }
                 }
 
@@ -237,32 +318,237 @@ impl<'s> System<'s> for MovePlayerSystem {
             }
         }
 
+        for (from, to, tribe) in player_moves {
+            grid.move_player(&from, &to, tribe);
+        }
+
+        //blocks get moved after the tribes, in the same order they were pushed
+        //swapping modes swap whole tribes around once per move, rather than moving each tribe member
+        if swap_now {
+            let members: Vec<_> = (&entities, &players, &tiles)
+                .join()
+                .map(|(e, p, t)| (e, p.id, *t))
+                .collect();
+            let positions: Vec<(usize, TileTransform)> =
+                members.iter().map(|(_, tribe, t)| (*tribe, *t)).collect();
+
+            for (a, b) in swap_pairs(&positions, control.selected) {
+                for &(i, j) in &[(a, b), (b, a)] {
+                    let (e, tribe, from) = members[i];
+                    let to = members[j].2;
+                    if let (Some(tile), Some(movement_anim), Some(rot_anim)) = (
+                        tiles.get_mut(e),
+                        movement_animators.get_mut(e),
+                        rotation_animators.get_mut(e),
+                    ) {
+                        set_tiletransform_with_anim(
+                            tile,
+                            to,
+                            movement_anim,
+                            rot_anim,
+                            SWAP_ANIM_LEN,
+                            AnimInterpolation::ReverseExponential,
+                        );
+                    }
+                    grid.move_player(&from, &to, tribe);
+                    tribes_moved.insert(tribe);
+                    events.single_write(GameEvent::TribeMoved { tribe, from, to });
+                }
+                add_to_score = mode.adds_to_score();
+            }
+        }
+
+        for (from, to, anim_len, interp) in box_moves {
+            //two tribe members can push the same block at once, but it only moves once
+            if !grid.is_pushable(&from) {
+                continue;
+            }
+            grid.move_pushable(&from, &to);
+
+            for (tile, _, movement_anim) in (&mut tiles, &pushables, &mut movement_animators).join()
+            {
+                if tile == &from {
+                    movement_anim
+                        .replace_data(MovementAnimationData::new(from, to, anim_len, interp));
+                    tile.set(to);
+                    break;
+                }
+            }
+        }
+
+        //special floors get applied after everything else has moved, in entity order so that tribe members entering the same teleporter always end up the same way
+        moved.sort();
+        for e in moved {
+            let (tile, player) = match (tiles.get_mut(e), players.get(e)) {
+                (Some(tile), Some(player)) => (tile, player),
+                _ => continue,
+            };
+            let (dest, effect) = match floor_destination(*tile, &grid, mode) {
+                Some(floor) => floor,
+                None => continue,
+            };
+
+            let from = *tile;
+            match effect {
+                FloorEffect::Teleporter(_) => {
+                    if let Some(anim) = movement_animators.get_mut(e) {
+                        anim.replace_data(MovementAnimationData::new(
+                            dest,
+                            dest,
+                            0.0,
+                            AnimInterpolation::Linear,
+                        ));
+                    }
+                    if let Some(anim) = scale_animators.get_mut(e) {
+                        anim.replace_data(ScaleAnimationData::new(
+                            TELEPORT_ANIM_LEN,
+                            AnimInterpolation::ReverseExponential,
+                        ));
+                    }
+                }
+                FloorEffect::OneWay(_) => {
+                    if let Some(anim) = movement_animators.get_mut(e) {
+                        anim.replace_data(MovementAnimationData::new(
+                            from,
+                            dest,
+                            ONE_WAY_ANIM_LEN,
+                            AnimInterpolation::Linear,
+                        ));
+                    }
+                }
+            }
+            tile.set(dest);
+            grid.move_player(&from, &dest, player.id);
+            events.single_write(GameEvent::TribeMoved {
+                tribe: player.id,
+                from,
+                to: dest,
+            });
+        }
+
+        for tribe in tribes_moved {
+            control.add_move(tribe);
+        }
+
         if add_to_score {
             gws.level_no_of_moves += 1;
-            gm.do_move();
+            gm.do_move(&mut events);
         }
     }
 }
 
-///Checks whether a proposed TileTransform is in a valid position, given tiles it needs to avoid, using the consts in main.rs for OOB detection.
-pub fn tile_works(proposed_tile: TileTransform, collision_tiles: &[TileTransform]) -> bool {
-    let mut res = true;
+///Tiles which things are moving onto this frame, as the grid only gets updated after everything has picked where to go
+#[derive(Clone, Debug, Default)]
+pub struct ClaimedTiles {
+    ///Tiles which tribe members are moving onto - other tribe members can still move onto these, as that is how they merge
+    pub tribes: HashSet<TileTransform>,
+    ///Tiles which blocks are being pushed onto
+    pub blocks: HashSet<TileTransform>,
+}
+impl ClaimedTiles {
+    ///Whether or not a tribe member can move onto a tile without landing on a block that is being pushed there
+    pub fn free_for_tribe(&self, t: &TileTransform) -> bool {
+        !self.blocks.contains(t)
+    }
+
+    ///Whether or not a block can be pushed onto a tile without landing on anything else that is moving there
+    pub fn free_for_block(&self, t: &TileTransform) -> bool {
+        !self.blocks.contains(t) && !self.tribes.contains(t)
+    }
+}
+
+///What happens to a block when a tribe member moves
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Push {
+    ///There is no block on the path
+    Clear,
+    ///There is a block on the path, but it can't be pushed (or a wall is in the way before it)
+    Blocked,
+    ///A block gets pushed - (from, to)
+    Moves(TileTransform, TileTransform),
+}
 
-    if proposed_tile.x < 0
-        || proposed_tile.y < 0
-        || proposed_tile.x > WIDTH as i32 - 1
-        || proposed_tile.y > HEIGHT as i32 - 1
-    {
-        res = false;
+///Checks whether a move would push a block, and gets where the block would go
+///
+///The path is walked one tile at a time, so a block partway along a long move (eg. 3 tiles in Frenzy) gets pushed rather than jumped over. The block stays one tile ahead of the tribe member for the rest of the move, and every tile it passes through has to be free.
+///
+///  - **from** is where the tribe member currently is
+///  - **proposed_tile** is where the tribe member wants to go
+///  - **grid** is the grid of what is on each tile
+///  - **mode** is the current mode - some modes walk straight over blocks
+///  - **claimed** is the tiles which other things are already moving onto this frame
+pub fn get_push(
+    from: TileTransform,
+    proposed_tile: TileTransform,
+    grid: &OccupancyGrid,
+    mode: &dyn SpecialMode,
+    claimed: &ClaimedTiles,
+) -> Push {
+    if !mode.pushes_blocks() {
+        return Push::Clear;
     }
 
-    for possibility in collision_tiles {
-        if &proposed_tile == possibility {
-            res = false;
+    let delta = proposed_tile - from;
+    let step = TileTransform::new(delta.x.signum(), delta.y.signum());
+    //moves which aren't in a straight line (eg. Crazy) have no path to walk, so only the tile landed on counts
+    let (mut pos, steps) = if delta.x != 0 && delta.y != 0 {
+        (proposed_tile - step, 1)
+    } else {
+        (from, delta.x.abs().max(delta.y.abs()))
+    };
+
+    let free = |t: TileTransform| {
+        mode.tile_works(t, grid) && grid.players(&t).is_empty() && claimed.free_for_block(&t)
+    };
+    let mut wall_on_path = false;
+
+    for i in 1..=steps {
+        pos = pos + step;
+
+        if grid.is_pushable(&pos) {
+            if wall_on_path {
+                return Push::Blocked;
+            }
+
+            let mut dest = pos;
+            for _ in i..=steps {
+                dest = dest + step;
+                if !free(dest) {
+                    return Push::Blocked;
+                }
+            }
+            return Push::Moves(pos, dest);
+        }
+
+        if i < steps && !mode.tile_works(pos, grid) {
+            wall_on_path = true;
         }
     }
 
-    res
+    Push::Clear
+}
+
+///Gets where a special floor sends a tribe member after it steps onto it, or None if it stays put
+///
+///  - **t** is where the tribe member is
+///  - **grid** is the grid of what is on each tile
+///  - **mode** is the current mode - the destination has to be a tile the mode can move onto
+pub fn floor_destination(
+    t: TileTransform,
+    grid: &OccupancyGrid,
+    mode: &dyn SpecialMode,
+) -> Option<(TileTransform, FloorEffect)> {
+    let effect = grid.floor(&t)?;
+    let dest = match effect {
+        FloorEffect::Teleporter(_) => grid.teleporter_exit(&t)?,
+        FloorEffect::OneWay(dir) => t + dir,
+    };
+
+    if mode.tile_works(dest, grid) {
+        Some((dest, effect))
+    } else {
+        None
+    }
 }
 
 ///Sets one tiletransform equal to another with the lt_animations, and a given duration. Also adds a rotation
@@ -281,3 +567,147 @@ pub fn set_tiletransform_with_anim(
     ));
     from.set(to);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lonely_tribes_components::{special_modes::ModeRegistry, win_related::GamePlayingMode};
+
+    fn t(x: i32, y: i32) -> TileTransform {
+        TileTransform::new(x, y)
+    }
+
+    #[test]
+    pub fn frenzy_push_test() {
+        let registry = ModeRegistry::default();
+        let frenzy = registry.get(GamePlayingMode::Frenzy);
+        let claimed = ClaimedTiles::default();
+        let mut grid = OccupancyGrid::empty();
+        grid.add_pushable(&t(3, 2));
+
+        assert_eq!(
+            get_push(t(2, 2), t(5, 2), &grid, frenzy, &claimed),
+            Push::Moves(t(3, 2), t(6, 2)),
+            "Block stays one tile ahead for the whole move"
+        );
+
+        grid.add_collider(&t(5, 2));
+        assert_eq!(
+            get_push(t(2, 2), t(5, 2), &grid, frenzy, &claimed),
+            Push::Blocked,
+            "Wall on the block's path"
+        );
+    }
+
+    #[test]
+    pub fn wall_before_block_test() {
+        let registry = ModeRegistry::default();
+        let frenzy = registry.get(GamePlayingMode::Frenzy);
+        let claimed = ClaimedTiles::default();
+        let mut grid = OccupancyGrid::empty();
+        grid.add_collider(&t(3, 2));
+
+        assert_eq!(
+            get_push(t(2, 2), t(5, 2), &grid, frenzy, &claimed),
+            Push::Clear,
+            "Frenzy still dashes over walls when there is no block"
+        );
+
+        grid.add_pushable(&t(4, 2));
+        assert_eq!(
+            get_push(t(2, 2), t(5, 2), &grid, frenzy, &claimed),
+            Push::Blocked
+        );
+    }
+
+    #[test]
+    pub fn nudger_push_test() {
+        let registry = ModeRegistry::default();
+        let nudger = registry.get(GamePlayingMode::Nudger);
+        let mut grid = OccupancyGrid::empty();
+        grid.add_pushable(&t(3, 2));
+
+        assert_eq!(
+            get_push(t(2, 2), t(3, 2), &grid, nudger, &ClaimedTiles::default()),
+            Push::Clear
+        );
+    }
+
+    #[test]
+    pub fn blocked_destination_test() {
+        let registry = ModeRegistry::default();
+        let boring = registry.get(GamePlayingMode::Boring);
+        let claimed = ClaimedTiles::default();
+        let mut grid = OccupancyGrid::empty();
+        grid.add_pushable(&t(3, 2));
+
+        assert_eq!(
+            get_push(t(2, 2), t(3, 2), &grid, boring, &claimed),
+            Push::Moves(t(3, 2), t(4, 2))
+        );
+
+        grid.add_player(&t(4, 2), 1);
+        assert_eq!(
+            get_push(t(2, 2), t(3, 2), &grid, boring, &claimed),
+            Push::Blocked
+        );
+
+        grid.add_pushable(&t(3, 3));
+        grid.add_pushable(&t(3, 4));
+        assert_eq!(
+            get_push(t(3, 2), t(3, 3), &grid, boring, &claimed),
+            Push::Blocked,
+            "Can't push a block into another block"
+        );
+    }
+
+    #[test]
+    pub fn claimed_tiles_test() {
+        let registry = ModeRegistry::default();
+        let boring = registry.get(GamePlayingMode::Boring);
+        let mut grid = OccupancyGrid::empty();
+        grid.add_pushable(&t(3, 2));
+
+        let mut claimed = ClaimedTiles::default();
+        claimed.tribes.insert(t(4, 2));
+        assert_eq!(
+            get_push(t(2, 2), t(3, 2), &grid, boring, &claimed),
+            Push::Blocked,
+            "Another tribe member is moving onto the block's destination"
+        );
+        assert!(claimed.free_for_tribe(&t(4, 2)));
+
+        let mut claimed = ClaimedTiles::default();
+        claimed.blocks.insert(t(4, 2));
+        assert_eq!(
+            get_push(t(2, 2), t(3, 2), &grid, boring, &claimed),
+            Push::Blocked,
+            "Another block is being pushed onto the same tile"
+        );
+        assert!(!claimed.free_for_tribe(&t(4, 2)));
+    }
+
+    #[test]
+    pub fn floor_destination_test() {
+        let registry = ModeRegistry::default();
+        let boring = registry.get(GamePlayingMode::Boring);
+        let mut grid = OccupancyGrid::empty();
+        let arrow = FloorEffect::OneWay(t(1, 0));
+        grid.add_floor(&t(2, 2), arrow);
+        grid.add_floor(&t(1, 1), FloorEffect::Teleporter(0));
+        grid.add_floor(&t(9, 4), FloorEffect::Teleporter(0));
+
+        assert_eq!(
+            floor_destination(t(2, 2), &grid, boring),
+            Some((t(3, 2), arrow))
+        );
+        assert_eq!(
+            floor_destination(t(1, 1), &grid, boring),
+            Some((t(9, 4), FloorEffect::Teleporter(0)))
+        );
+        assert_eq!(floor_destination(t(5, 5), &grid, boring), None);
+
+        grid.add_collider(&t(3, 2));
+        assert_eq!(floor_destination(t(2, 2), &grid, boring), None);
+    }
+}
@@ -0,0 +1,118 @@
+use amethyst::{
+    assets::AssetStorage,
+    audio::{
+        output::{init_output, Output},
+        AudioSink, Source, SourceHandle,
+    },
+    core::{
+        ecs::{Read, System, SystemData, World},
+        Time,
+    },
+};
+use lonely_tribes_lib::{
+    audio::{Muzac, CROSSFADE_TIME, MUSIC_VOLUME},
+    config::LTConfig,
+};
+
+///System to play the music that the Muzac resource asks for, crossfading between tracks when that changes
+///
+///Uses 2 sinks, so that the old track can fade out while the new one fades in
+#[derive(Default)]
+pub struct MusicDirectorSystem {
+    ///The 2 sinks - None if there is no audio output
+    sinks: Option<[AudioSink; 2]>,
+    ///Index of the sink with the current track
+    current: usize,
+    ///Name of the current track, and whether or not the intro still needs to be queued
+    playing: Option<(String, bool)>,
+    ///How far through the crossfade we are, from 0.0 to 1.0
+    fade: f32,
+}
+
+impl<'s> System<'s> for MusicDirectorSystem {
+    type SystemData = (
+        Option<Read<'s, Output>>,
+        Option<Read<'s, Muzac>>,
+        Read<'s, AssetStorage<Source>>,
+        Read<'s, LTConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (output, muzac, storage, config, time): Self::SystemData) {
+        let (output, muzac) = match (output, muzac) {
+            (Some(o), Some(m)) => (o, m),
+            _ => return,
+        };
+        let sinks = match &mut self.sinks {
+            Some(s) => s,
+            None => return,
+        };
+
+        let desired = muzac.desired_track();
+        let track = match muzac.get_track(desired) {
+            Some(t) => t,
+            None => return,
+        };
+
+        let is_new = self
+            .playing
+            .as_ref()
+            .map(|(name, _)| name != desired)
+            .unwrap_or(true);
+        if is_new {
+            log::info!("Changing music to {}", desired);
+
+            self.current = 1 - self.current;
+            sinks[self.current] = AudioSink::new(&output);
+            sinks[self.current].set_volume(0.0);
+            self.playing = Some((desired.to_string(), track.intro.is_some()));
+            self.fade = 0.0;
+        }
+
+        //Queue up the next part of the track
+        if let Some((_, needs_intro)) = &mut self.playing {
+            let sink = &sinks[self.current];
+            if sink.empty() {
+                let next = if *needs_intro {
+                    track.intro.as_ref()
+                } else {
+                    Some(&track.looped)
+                };
+
+                if let Some(source) = next.and_then(|h: &SourceHandle| storage.get(h)) {
+                    sink.append(source).unwrap_or_else(|err| {
+                        log::warn!("Unable to play music track {}: {}", desired, err)
+                    });
+                    *needs_intro = false;
+                }
+            }
+        }
+
+        //Crossfade
+        let vol = MUSIC_VOLUME * config.conf.vol;
+        let other = 1 - self.current;
+        if self.fade < 1.0 {
+            self.fade = (self.fade + time.delta_seconds() / CROSSFADE_TIME).min(1.0);
+            if self.fade >= 1.0 {
+                //dropping the old sink stops it
+                sinks[other] = AudioSink::new(&output);
+            }
+        }
+        sinks[self.current].set_volume(vol * self.fade);
+        sinks[other].set_volume(vol * (1.0 - self.fade));
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+
+        if world.try_fetch::<Output>().is_none() {
+            init_output(world);
+        }
+        self.sinks = world
+            .try_fetch::<Output>()
+            .map(|output| [AudioSink::new(&output), AudioSink::new(&output)]);
+        if self.sinks.is_none() {
+            log::warn!("No audio output found, so there will be no music");
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::player_overlap_checker::DeleteList;
+use amethyst::core::{
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, World, Write, WriteStorage},
+    shrev::ReaderId,
+};
+use lonely_tribes_animations::{
+    animation::Animator, interpolation::AnimInterpolation, movement::MovementAnimationData,
+};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    npcs::Npc,
+    occupancy::OccupancyGrid,
+    player::Player,
+    special_modes::{BoringMode, SpecialMode},
+    tile_transform::TileTransform,
+    win_related::{GameState, GameStateEnum},
+};
+use std::collections::HashSet;
+
+///Length of the NPC movement animation
+pub const NPC_MOVEMENT_ANIM_LEN: f32 = 0.15;
+
+///System to move NPCs one step after each player move
+///
+///NPCs use the normal collision rules whatever mode the tribes are in, can't walk through each other, and walk straight into tribe members - the HazardSystem then gets rid of anyone they touch
+#[derive(Default)]
+pub struct NpcSystem {
+    ///ReaderId for the game events, to find out when the tribes have moved
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'s> System<'s> for NpcSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, TileTransform>,
+        WriteStorage<'s, Npc>,
+        WriteStorage<'s, Animator<MovementAnimationData>>,
+        Read<'s, OccupancyGrid>,
+        Read<'s, DeleteList>,
+        Read<'s, GameState>,
+        Read<'s, GameEventChannel>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            players,
+            mut tiles,
+            mut npcs,
+            mut movement_animators,
+            grid,
+            delete_list,
+            gs,
+            events,
+        ): Self::SystemData,
+    ) {
+        //the first tribe member to move gives the direction of the player's input
+        let input = match &mut self.reader {
+            Some(reader) => events
+                .read(reader)
+                .filter_map(|ev| match ev {
+                    GameEvent::TribeMoved { from, to, .. } => Some(*to - *from),
+                    _ => None,
+                })
+                .next(),
+            None => return,
+        };
+        let input = match input {
+            Some(input) => input,
+            None => return,
+        };
+        if gs.ws != GameStateEnum::ToBeDecided || grid.needs_rebuild() {
+            return;
+        }
+
+        let tribe_members: Vec<TileTransform> = (&entities, &players, &tiles)
+            .join()
+            .filter(|(e, _, _)| !delete_list.0.contains(e))
+            .map(|(_, _, t)| *t)
+            .collect();
+        let mut taken: HashSet<TileTransform> = (&npcs, &tiles).join().map(|(_, t)| *t).collect();
+
+        for (npc, tile, movement_anim) in (&mut npcs, &mut tiles, &mut movement_animators).join() {
+            let from = *tile;
+            //NPCs which have just been walked into stay put, so tribe members can't swap places with them
+            if !grid.players(&from).is_empty() {
+                continue;
+            }
+
+            let proposed_tile = npc.proposed_tile(from, input, &tribe_members);
+            if proposed_tile == from
+                || taken.contains(&proposed_tile)
+                || !BoringMode.tile_works(proposed_tile, &grid)
+            {
+                continue;
+            }
+
+            taken.remove(&from);
+            taken.insert(proposed_tile);
+            movement_anim.replace_data(MovementAnimationData::new(
+                from,
+                proposed_tile,
+                NPC_MOVEMENT_ANIM_LEN,
+                AnimInterpolation::ReverseExponential,
+            ));
+            tile.set(proposed_tile);
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
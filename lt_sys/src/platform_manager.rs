@@ -0,0 +1,13 @@
+use amethyst::core::ecs::{System, Write};
+use lonely_tribes_lib::platform::Platform;
+
+///System to run the callbacks for the current platform, eg. Steam
+pub struct PlatformManager;
+
+impl<'s> System<'s> for PlatformManager {
+    type SystemData = Write<'s, Platform>;
+
+    fn run(&mut self, mut platform: Self::SystemData) {
+        platform.0.run_callbacks();
+    }
+}
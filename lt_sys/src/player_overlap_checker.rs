@@ -1,5 +1,8 @@
 use amethyst::core::ecs::{Entities, Entity, Join, ReadStorage, System, Write, WriteStorage};
 use lonely_tribes_components::{
+    boss::Boss,
+    events::{GameEvent, GameEventChannel},
+    occupancy::OccupancyGrid,
     player::Player,
     point_light::PointLight,
     tile_transform::TileTransform,
@@ -14,49 +17,80 @@ impl<'s> System<'s> for PlayerOverlapChecker {
         WriteStorage<'s, Player>,
         WriteStorage<'s, PointLight>,
         ReadStorage<'s, TileTransform>,
+        ReadStorage<'s, Boss>,
         Entities<'s>,
         Write<'s, DeleteList>,
         Write<'s, GameState>,
+        Write<'s, GameEventChannel>,
+        Write<'s, OccupancyGrid>,
     );
 
     fn run(
         &mut self,
-        (mut players, mut lights, tiles, entities, mut delete_list, mut gs): Self::SystemData,
+        (
+            mut players,
+            mut lights,
+            tiles,
+            bosses,
+            entities,
+            mut delete_list,
+            mut gs,
+            mut events,
+            mut grid,
+        ): Self::SystemData,
     ) {
         if gs.ws == GameStateEnum::ToBeDecided {
-            let mut map: HashMap<TileTransform, &mut Player> = HashMap::new();
-            let mut id_list = Vec::new();
+            //the member that any others on the same tile merge into
+            let mut survivors: HashMap<TileTransform, Entity> = HashMap::new();
+            //the tile of the first member found from each tribe, to check whether every tribe is together
+            let mut tribe_tiles: HashMap<usize, TileTransform> = HashMap::new();
+            let mut merges = Vec::new();
+            let mut together = true;
             let mut lost = false;
 
-            for (e, p, t) in (&entities, &mut players, &tiles).join() {
-                let p_id = p.id;
-                let to_insert = if let Some(mut current) = map.remove(t) {
-                    if current.id == p_id {
-                        current.no_players += 1;
-                        delete_list.0.push(e);
-                    } else {
-                        gs.ws = GameStateEnum::End {
-                            lost_position: Some(*t),
-                        };
-                        lost = true;
-                        break;
-                    }
-                    current
-                } else {
-                    p
-                };
+            for (e, p, t) in (&entities, &players, &tiles).join() {
+                if delete_list.0.contains(&e) {
+                    continue;
+                }
+
+                if grid.players(t).iter().any(|id| *id != p.id) {
+                    gs.ws = GameStateEnum::End {
+                        lost_position: Some(*t),
+                    };
+                    lost = true;
+                    events.single_write(GameEvent::LevelLost { at: *t });
+                    break;
+                }
 
-                if !id_list.contains(&p_id) {
-                    id_list.push(p_id);
+                if let Some(survivor) = survivors.get(t) {
+                    merges.push((*survivor, *p));
+                    delete_list.0.push(e);
+                    grid.remove_player(t, p.id);
+                    events.single_write(GameEvent::TribesMerged {
+                        tribe: p.id,
+                        at: *t,
+                    });
+                    continue;
                 }
+                survivors.insert(*t, e);
 
-                map.insert(*t, to_insert);
+                if tribe_tiles.entry(p.id).or_insert(*t) != t {
+                    together = false;
+                }
+            }
+
+            for (survivor, merged) in merges {
+                if let Some(p) = players.get_mut(survivor) {
+                    p.merge(&merged);
+                }
             }
 
-            if map.len() == id_list.len() && !lost {
+            //boss levels are won by defeating the boss instead
+            if together && !lost && bosses.join().next().is_none() {
                 gs.ws = GameStateEnum::End {
                     lost_position: None,
                 };
+                events.single_write(GameEvent::LevelWon);
             }
         }
 
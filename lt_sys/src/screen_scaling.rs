@@ -0,0 +1,104 @@
+use amethyst::{
+    core::{
+        ecs::{
+            Join, Read, ReadExpect, ReadStorage, System, SystemData, World, Write, WriteStorage,
+        },
+        shrev::{EventChannel, ReaderId},
+        Transform,
+    },
+    renderer::Camera,
+    ui::{UiText, UiTransform},
+    window::ScreenDimensions,
+    winit::{Event, WindowEvent},
+};
+use lonely_tribes_components::{screen_scaled::ScreenScaled, text_wobble::TextWobble};
+use lonely_tribes_lib::{
+    config::LTConfig,
+    states_util::{get_camera_dimensions, get_scaling_factor_from_conf, update_camera},
+};
+
+///System to keep the screen dimensions in the config up to date when the window is resized
+#[derive(Default)]
+pub struct ScreenDimensionsSystem {
+    ///ReaderId for the window events
+    reader: Option<ReaderId<Event>>,
+}
+
+impl<'s> System<'s> for ScreenDimensionsSystem {
+    type SystemData = (
+        Read<'s, EventChannel<Event>>,
+        ReadExpect<'s, ScreenDimensions>,
+        Write<'s, LTConfig>,
+    );
+
+    fn run(&mut self, (events, screen_dimensions, mut config): Self::SystemData) {
+        let mut latest = None;
+        if let Some(reader) = &mut self.reader {
+            for event in events.read(reader) {
+                if let Event::WindowEvent {
+                    event: WindowEvent::Resized(size),
+                    ..
+                } = event
+                {
+                    let physical = size.to_physical(screen_dimensions.hidpi_factor());
+                    latest = Some((physical.width as u32, physical.height as u32));
+                }
+            }
+        }
+
+        if let Some(current) = latest {
+            //minimising gives a 0x0 window, which nothing can be laid out in
+            if current.0 != 0 && current.1 != 0 && current != config.conf.screen_dimensions {
+                log::info!("Screen resized to {:?}", current);
+                config.set_screen_dimensions(current);
+            }
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<EventChannel<Event>>().register_reader());
+    }
+}
+
+///System to recompute the camera, and all ScreenScaled UI, when the config changes
+#[derive(Default)]
+pub struct ScreenScalingSystem {
+    ///The version of the config that everything was last scaled for
+    last_version: Option<usize>,
+}
+
+impl<'s> System<'s> for ScreenScalingSystem {
+    type SystemData = (
+        Read<'s, LTConfig>,
+        WriteStorage<'s, Camera>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, ScreenScaled>,
+        WriteStorage<'s, UiTransform>,
+        WriteStorage<'s, UiText>,
+        WriteStorage<'s, TextWobble>,
+    );
+
+    fn run(
+        &mut self,
+        (config, mut cameras, mut transforms, scaleds, mut ui_transforms, mut txts, mut wobbles): Self::SystemData,
+    ) {
+        if self.last_version == Some(config.version()) {
+            return;
+        }
+        self.last_version = Some(config.version());
+
+        let dims = get_camera_dimensions(&config.conf);
+        for (camera, trans) in (&mut cameras, &mut transforms).join() {
+            update_camera(camera, trans, dims);
+        }
+
+        let sf = get_scaling_factor_from_conf(&config.conf);
+        for (scaled, trans, txt) in (&scaleds, &mut ui_transforms, (&mut txts).maybe()).join() {
+            scaled.apply(sf, trans, txt);
+        }
+        for (_, trans, wobble) in (&scaleds, &ui_transforms, &mut wobbles).join() {
+            wobble.old_y = trans.local_y;
+        }
+    }
+}
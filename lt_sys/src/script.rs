@@ -0,0 +1,169 @@
+use crate::{
+    message_system::{MessageList, MESSAGE_PER_LETTER},
+    move_player::MovementDisabler,
+    update_tile_transforms::UpdateTileTransforms,
+};
+use amethyst::{
+    core::{
+        ecs::{
+            Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, World, Write,
+            WriteStorage,
+        },
+        shrev::ReaderId,
+        transform::Transform,
+        Time,
+    },
+    renderer::{palette::Srgba, resources::Tint, Camera},
+};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    script::{CameraPan, Highlight, LevelScript, ScriptAction, ScriptTrigger},
+    tile_transform::TileTransform,
+    win_related::{GameState, GameStateEnum},
+};
+use lonely_tribes_lib::{
+    config::LTConfig,
+    states_util::{get_camera_dimensions, get_grid_offset},
+};
+use std::collections::VecDeque;
+
+///System to run the level script - it checks the triggers, and then shows messages, pans the camera and highlights tiles
+#[derive(Default)]
+pub struct ScriptSystem {
+    ///ReaderId for the GameEvents
+    reader: Option<ReaderId<GameEvent>>,
+    ///Camera pans which are going, or waiting to go
+    pans: VecDeque<CameraPan>,
+}
+
+impl<'s> System<'s> for ScriptSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, Time>,
+        Write<'s, LevelScript>,
+        Read<'s, GameEventChannel>,
+        Read<'s, GameState>,
+        Write<'s, MessageList>,
+        Write<'s, MovementDisabler>,
+        Read<'s, LTConfig>,
+        ReadStorage<'s, Camera>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Highlight>,
+        WriteStorage<'s, Tint>,
+        Read<'s, LazyUpdate>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            time,
+            mut script,
+            events,
+            gws,
+            mut messages,
+            mut disabler,
+            config,
+            cameras,
+            mut transforms,
+            mut highlights,
+            mut tints,
+            lazy,
+        ): Self::SystemData,
+    ) {
+        let delta = time.delta_seconds();
+        script.timer += delta;
+        script.holding_for = (script.holding_for - delta).max(0.0);
+
+        let mut happened = Vec::new();
+        if let Some(reader) = &mut self.reader {
+            for event in events.read(reader) {
+                if let GameEvent::LevelStarted { .. } = event {
+                    //pans from the last level shouldn't carry on into this one
+                    self.pans.clear();
+                }
+                happened.extend(ScriptTrigger::from_event(event));
+            }
+        }
+        let won = happened.contains(&ScriptTrigger::Won);
+
+        let mut action_time = 0.0;
+        for action in script.take_triggered(gws.level_no_of_moves, &happened) {
+            match action {
+                ScriptAction::Message(msg) => {
+                    action_time += MESSAGE_PER_LETTER * msg.len() as f32;
+                    messages.push(msg);
+                }
+                ScriptAction::PanCamera { x, y, hold } => {
+                    let pan = CameraPan::new((x, y), hold);
+                    action_time += pan.total_time();
+                    self.pans.push_back(pan);
+                }
+                ScriptAction::Highlight { tiles, time } => {
+                    action_time += time;
+                    if let Some(sprite) = &script.highlight_sprite {
+                        for (x, y) in tiles {
+                            let mut trans = Transform::default();
+                            trans.set_translation_z(0.3);
+
+                            lazy.create_entity(&entities)
+                                .with(sprite.clone())
+                                .with(TileTransform::new(x as i32, y as i32))
+                                .with(trans)
+                                .with(Tint(Srgba::new(1.0, 0.9, 0.3, 1.0)))
+                                .with(Highlight::new(time))
+                                .build();
+                        }
+                    }
+                }
+            }
+        }
+        if won {
+            script.holding_for += action_time;
+        }
+
+        for (e, highlight, tint) in (&entities, &mut highlights, &mut tints).join() {
+            match highlight.tick(delta) {
+                Some(alpha) => tint.0.alpha = alpha,
+                None => entities
+                    .delete(e)
+                    .unwrap_or_else(|err| log::warn!("Unable to delete highlight: {}", err)),
+            }
+        }
+
+        if let Some(pan) = self.pans.front_mut() {
+            pan.so_far += delta;
+            disabler.enabled = true;
+
+            let (w, h) = get_camera_dimensions(&config.conf);
+            let (start_x, start_y) = (w * 0.5, h * 0.5);
+            let (target_x, target_y) = UpdateTileTransforms::tile_to_xyz(
+                TileTransform::new(pan.target.0 as i32, pan.target.1 as i32),
+                get_grid_offset(&config.conf),
+            );
+            let progress = pan.progress();
+
+            for (_, trans) in (&cameras, &mut transforms).join() {
+                let z = trans.translation().z;
+                trans.set_translation_xyz(
+                    start_x + (target_x - start_x) * progress,
+                    start_y + (target_y - start_y) * progress,
+                    z,
+                );
+            }
+
+            if pan.is_done() {
+                self.pans.pop_front();
+                //losing disables movement too, so don't turn it back on then
+                if self.pans.is_empty() && !matches!(gws.ws, GameStateEnum::End { .. }) {
+                    disabler.enabled = false;
+                }
+            }
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
@@ -0,0 +1,162 @@
+use amethyst::{
+    assets::AssetStorage,
+    audio::{
+        output::{init_output, Output},
+        Source,
+    },
+    core::{
+        ecs::{Read, System, SystemData, World, Write},
+        shrev::{EventChannel, ReaderId},
+        Time,
+    },
+    ui::{UiEvent, UiEventType},
+};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    win_related::GamePlayingMode,
+};
+use lonely_tribes_lib::{
+    audio::{Sfx, SfxQueue, SoundEffect, SFX_VOLUME},
+    config::LTConfig,
+};
+
+///System to play all of the sound effects in the SfxQueue
+///
+///If there is no audio output, or the sound effects are disabled, the queue just gets cleared
+pub struct SfxSystem;
+
+impl<'s> System<'s> for SfxSystem {
+    type SystemData = (
+        Write<'s, SfxQueue>,
+        Option<Read<'s, Output>>,
+        Option<Read<'s, Sfx>>,
+        Read<'s, AssetStorage<Source>>,
+        Read<'s, LTConfig>,
+    );
+
+    fn run(&mut self, (mut queue, output, sfx, storage, config): Self::SystemData) {
+        let queued = std::mem::take(&mut queue.0);
+        let vol = SFX_VOLUME * config.conf.sfx_vol;
+
+        if let (Some(output), Some(sfx)) = (output, sfx) {
+            if vol <= 0.0 {
+                return;
+            }
+
+            for effect in queued {
+                if let Some(source) = sfx.get(effect).and_then(|h| storage.get(h)) {
+                    output.play_once(source, vol);
+                }
+            }
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+
+        //the MusicDirectorSystem isn't added when the music is off, so the output might not be set up yet
+        if world.try_fetch::<Output>().is_none() {
+            init_output(world);
+        }
+        if world.try_fetch::<Output>().is_none() {
+            log::warn!("No audio output found, so there will be no sound effects");
+        }
+    }
+}
+
+///System to queue up sound effects for hovering over and clicking on buttons
+#[derive(Default)]
+pub struct UiSfxSystem {
+    ///ReaderId for the UI events
+    reader: Option<ReaderId<UiEvent>>,
+}
+
+impl<'s> System<'s> for UiSfxSystem {
+    type SystemData = (Read<'s, EventChannel<UiEvent>>, Write<'s, SfxQueue>);
+
+    fn run(&mut self, (events, mut queue): Self::SystemData) {
+        if let Some(reader) = &mut self.reader {
+            for event in events.read(reader) {
+                match event.event_type {
+                    UiEventType::HoverStart => queue.0.push(SoundEffect::UiHover),
+                    UiEventType::ClickStop => queue.0.push(SoundEffect::UiClick),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<EventChannel<UiEvent>>().register_reader());
+    }
+}
+
+///Minimum time between bump sounds, so that holding a direction into a wall doesn't spam them
+const BUMP_INTERVAL: f32 = 0.25;
+
+///System to queue up sound effects for GameEvents
+#[derive(Default)]
+pub struct GameEventSfxSystem {
+    ///ReaderId for the game events
+    reader: Option<ReaderId<GameEvent>>,
+    ///Time since the last bump sound
+    since_bump: f32,
+}
+
+impl<'s> System<'s> for GameEventSfxSystem {
+    type SystemData = (
+        Read<'s, GameEventChannel>,
+        Write<'s, SfxQueue>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (events, mut queue, time): Self::SystemData) {
+        self.since_bump += time.delta_seconds();
+
+        let mut moved = false;
+        let mut bumped = false;
+        let mut merged = false;
+
+        if let Some(reader) = &mut self.reader {
+            for event in events.read(reader) {
+                match event {
+                    GameEvent::TribeMoved { .. } | GameEvent::BoxPushed { .. } => moved = true,
+                    GameEvent::TribeBumped { .. } => bumped = true,
+                    GameEvent::TribesMerged { .. } | GameEvent::BossHit { .. } => merged = true,
+                    GameEvent::TribeMemberLost { .. } => queue.0.push(SoundEffect::Lose),
+                    GameEvent::TribeSelected { .. } => queue.0.push(SoundEffect::UiClick),
+                    GameEvent::ModeChanged { to, .. } => {
+                        queue.0.push(if to == &GamePlayingMode::Boring {
+                            SoundEffect::ModeLeave
+                        } else {
+                            SoundEffect::ModeEnter
+                        })
+                    }
+                    GameEvent::LevelWon => queue.0.push(SoundEffect::Win),
+                    GameEvent::LevelLost { .. } => queue.0.push(SoundEffect::Lose),
+                    GameEvent::LevelStarted { .. }
+                    | GameEvent::GateToggled { .. }
+                    | GameEvent::TribeRespawned { .. }
+                    | GameEvent::MessagePosted(_) => {}
+                }
+            }
+        }
+
+        //Only one of each per frame, however many tribe members there are
+        if moved {
+            queue.0.push(SoundEffect::Step);
+        } else if bumped && self.since_bump > BUMP_INTERVAL {
+            queue.0.push(SoundEffect::Bump);
+            self.since_bump = 0.0;
+        }
+        if merged {
+            queue.0.push(SoundEffect::Merge);
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
@@ -0,0 +1,91 @@
+use amethyst::{
+    core::{
+        ecs::{Join, ReadStorage, System, SystemData, World, Write, WriteStorage},
+        shrev::ReaderId,
+    },
+    renderer::SpriteRender,
+};
+use lonely_tribes_components::{
+    colliders::Collider,
+    events::{GameEvent, GameEventChannel},
+    occupancy::OccupancyGrid,
+    switches::Linked,
+    tile_transform::TileTransform,
+};
+use std::collections::HashSet;
+
+///System to update pressure plates and switches, and open or close the gates linked to them
+#[derive(Default)]
+pub struct SwitchSystem {
+    ///ReaderId for the game events, to find out when tribe members step onto switches
+    reader: Option<ReaderId<GameEvent>>,
+}
+
+impl<'s> System<'s> for SwitchSystem {
+    type SystemData = (
+        ReadStorage<'s, TileTransform>,
+        WriteStorage<'s, Linked>,
+        WriteStorage<'s, Collider>,
+        WriteStorage<'s, SpriteRender>,
+        Write<'s, OccupancyGrid>,
+        Write<'s, GameEventChannel>,
+    );
+
+    fn run(
+        &mut self,
+        (tiles, mut linkeds, mut colliders, mut sprites, mut grid, mut events): Self::SystemData,
+    ) {
+        let stepped_on: HashSet<TileTransform> = match &mut self.reader {
+            Some(reader) => events
+                .read(reader)
+                .filter_map(|ev| match ev {
+                    GameEvent::TribeMoved { to, .. } => Some(*to),
+                    _ => None,
+                })
+                .collect(),
+            None => return,
+        };
+        if grid.needs_rebuild() {
+            return;
+        }
+
+        let mut active_links = HashSet::new();
+        for (t, linked, spr) in (&tiles, &mut linkeds, &mut sprites).join() {
+            if linked.is_gate() {
+                continue;
+            }
+
+            if linked.update_activator(grid.is_weighed_down(t), stepped_on.contains(t)) {
+                spr.sprite_number = linked.sprite();
+            }
+            if linked.active {
+                active_links.insert(linked.link());
+            }
+        }
+
+        for (t, linked, collider, spr) in
+            (&tiles, &mut linkeds, &mut colliders, &mut sprites).join()
+        {
+            if !linked.update_gate(&active_links, grid.is_weighed_down(t)) {
+                continue;
+            }
+
+            if linked.active {
+                grid.open_gate(t, linked.link());
+            } else {
+                grid.close_gate(t);
+            }
+            *collider = linked.collider();
+            spr.sprite_number = linked.sprite();
+            events.single_write(GameEvent::GateToggled {
+                at: *t,
+                open: linked.active,
+            });
+        }
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        Self::SystemData::setup(world);
+        self.reader = Some(world.fetch_mut::<GameEventChannel>().register_reader());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::update_tile_transforms::UpdateTileTransforms;
+use amethyst::{
+    core::ecs::{Join, Read, ReadStorage, System, Write, WriteStorage},
+    input::{InputHandler, StringBindings, VirtualKeyCode},
+    winit::MouseButton,
+};
+use lonely_tribes_animations::{
+    animation::Animator, interpolation::AnimInterpolation, rotation::RotationAnimationData,
+};
+use lonely_tribes_components::{
+    events::{GameEvent, GameEventChannel},
+    player::{Player, TribeControl},
+    tile_transform::TileTransform,
+};
+use lonely_tribes_lib::{
+    config::LTConfig,
+    states_util::{get_camera_dimensions, get_grid_offset},
+};
+
+///How long the wobble lasts on a newly selected tribe
+pub const SELECTED_WOBBLE_LEN: f32 = 0.3;
+
+///System to pick which tribe gets moved, for levels where tribes are controlled independently
+///
+///Tab cycles through the tribes, the number keys pick one by id (1 for tribe 0 etc.), and clicking on a tribe member picks its tribe
+#[derive(Default)]
+pub struct TribeSelectorSystem {
+    ///Whether or not Tab was down last frame
+    tab_was_down: bool,
+    ///Whether or not the left mouse button was down last frame
+    click_was_down: bool,
+}
+
+impl<'s> System<'s> for TribeSelectorSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, TileTransform>,
+        WriteStorage<'s, Animator<RotationAnimationData>>,
+        Read<'s, InputHandler<StringBindings>>,
+        Read<'s, LTConfig>,
+        Write<'s, TribeControl>,
+        Write<'s, GameEventChannel>,
+    );
+
+    fn run(
+        &mut self,
+        (players, tiles, mut rotation_animators, input, config, mut control, mut events): Self::SystemData,
+    ) {
+        let tab_down = input.key_is_down(VirtualKeyCode::Tab);
+        let click_down = input.mouse_button_is_down(MouseButton::Left);
+        let tab_pressed = tab_down && !self.tab_was_down;
+        let clicked = click_down && !self.click_was_down;
+        self.tab_was_down = tab_down;
+        self.click_was_down = click_down;
+
+        if !control.independent {
+            return;
+        }
+
+        let tribes: Vec<usize> = players.join().map(|p| p.id).collect();
+        if tribes.is_empty() {
+            return;
+        }
+        let old = control.selected;
+
+        //the selected tribe might not be in this level
+        if !tribes.contains(&control.selected) || tab_pressed {
+            control.select_next(&tribes);
+        }
+
+        use VirtualKeyCode::*;
+        for (i, key) in [Key1, Key2, Key3, Key4].iter().enumerate() {
+            if input.key_is_down(*key) && tribes.contains(&i) {
+                control.selected = i;
+            }
+        }
+
+        if clicked {
+            if let Some(pos) = input.mouse_position() {
+                let conf = &config.conf;
+                let (w, h) = conf.screen_dimensions;
+                let clicked_tile = UpdateTileTransforms::screen_to_tile(
+                    pos,
+                    (w as f32, h as f32),
+                    get_camera_dimensions(conf),
+                    get_grid_offset(conf),
+                );
+
+                if let Some((p, _)) = (&players, &tiles).join().find(|(_, t)| **t == clicked_tile) {
+                    control.selected = p.id;
+                }
+            }
+        }
+
+        if control.selected != old {
+            for (p, anim) in (&players, &mut rotation_animators).join() {
+                if p.id == control.selected {
+                    anim.replace_data(RotationAnimationData::new(
+                        SELECTED_WOBBLE_LEN,
+                        AnimInterpolation::ReverseExponential,
+                    ));
+                }
+            }
+            events.single_write(GameEvent::TribeSelected {
+                tribe: control.selected,
+            });
+        }
+    }
+}
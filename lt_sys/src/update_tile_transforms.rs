@@ -1,15 +1,15 @@
 use amethyst::core::{
     ecs::{Join, Read, System, WriteStorage},
+    math::Vector3,
     Time, Transform,
 };
 use lonely_tribes_animations::{
     animation::Animator, data::AnimationData, movement::MovementAnimationData,
-    rotation::RotationAnimationData,
+    rotation::RotationAnimationData, scale::ScaleAnimationData,
 };
 use lonely_tribes_components::tile_transform::TileTransform;
 use lonely_tribes_lib::{
-    states_util::{CAMERA_BASE_HEIGHT, CAMERA_BASE_WIDTH, CAMERA_DIMENSIONS},
-    HEIGHT, TILE_WIDTH_HEIGHT,
+    config::LTConfig, states_util::get_grid_offset, HEIGHT, TILE_WIDTH_HEIGHT,
 };
 
 /// System to turn TileTransforms into Transforms
@@ -26,16 +26,24 @@ impl<'s> System<'s> for UpdateTileTransforms {
         WriteStorage<'s, Transform>,
         WriteStorage<'s, Animator<MovementAnimationData>>,
         WriteStorage<'s, Animator<RotationAnimationData>>,
+        WriteStorage<'s, Animator<ScaleAnimationData>>,
         Read<'s, Time>,
+        Read<'s, LTConfig>,
     );
 
     fn run(
         &mut self,
-        (tiles, mut transforms, mut movement_animators, mut rotation_animators, time): Self::SystemData,
+        (
+            tiles,
+            mut transforms,
+            mut movement_animators,
+            mut rotation_animators,
+            mut scale_animators,
+            time,
+            config,
+        ): Self::SystemData,
     ) {
-        let dims = *CAMERA_DIMENSIONS;
-        let wide_x_offset = (dims.0 - CAMERA_BASE_WIDTH) / 2.0;
-        let wide_y_offset = (dims.1 - CAMERA_BASE_HEIGHT) / 2.0;
+        let (wide_x_offset, wide_y_offset) = get_grid_offset(&config.conf);
 
         for (tile, trans) in (&tiles, &mut transforms).join() {
             let old_z = trans.translation().z;
@@ -80,22 +88,55 @@ impl<'s> System<'s> for UpdateTileTransforms {
                 trans.set_rotation_2d(anim.get_current());
             }
         }
+        for (trans, anim_cmp) in (&mut transforms, &mut scale_animators).join() {
+            if anim_cmp.anim_is_done() {
+                anim_cmp.finish();
+                trans.set_scale(Vector3::from([1.0; 3]));
+            } else if let Some(anim) = &mut anim_cmp.animation_data {
+                anim.add_time(time.delta_seconds());
+                trans.set_scale(Vector3::from([anim.get_current(); 3]));
+            }
+        }
     }
 }
 
 impl UpdateTileTransforms {
     ///Convert a TileTransform to a Transform on Screen
+    ///
+    ///  - **offset** is the letterboxing/pillarboxing offset from `get_grid_offset`
     #[allow(dead_code)]
-    pub fn tile_to_transform(tile: TileTransform, z: f32) -> Transform {
+    pub fn tile_to_transform(tile: TileTransform, z: f32, offset: (f32, f32)) -> Transform {
         let mut trans = Transform::default();
-        let (x, y) = Self::tile_to_xyz(tile);
+        let (x, y) = Self::tile_to_xyz(tile, offset);
         trans.set_translation_xyz(x, y, z);
         trans
     }
+    ///Convert a position on the screen (eg. the mouse) into the TileTransform under it
+    ///
+    ///  - **screen_pos** is the position in pixels, from the top left of the window
+    ///  - **screen_dimensions** is the size of the window in pixels
+    ///  - **camera_dimensions** is the size of the camera from `get_camera_dimensions`
+    ///  - **offset** is the letterboxing/pillarboxing offset from `get_grid_offset`
+    pub fn screen_to_tile(
+        (screen_x, screen_y): (f32, f32),
+        (screen_w, screen_h): (f32, f32),
+        (camera_w, camera_h): (f32, f32),
+        (x_offset, y_offset): (f32, f32),
+    ) -> TileTransform {
+        let x = screen_x / screen_w * camera_w - x_offset;
+        let y = (1.0 - screen_y / screen_h) * camera_h - y_offset;
+
+        TileTransform::new(
+            (x / TILE_WIDTH_HEIGHT as f32).floor() as i32,
+            HEIGHT - 1 - (y / TILE_WIDTH_HEIGHT as f32).floor() as i32,
+        )
+    }
     ///Convert a TileTransform to an XYZ for a Transform on Screen
-    pub fn tile_to_xyz(tile: TileTransform) -> (f32, f32) {
-        let x = tile.x as f32 * TILE_WIDTH_HEIGHT as f32 + TILE_WIDTH;
-        let y = (HEIGHT - tile.y) as f32 * TILE_WIDTH_HEIGHT as f32 - TILE_HEIGHT;
+    ///
+    ///  - **offset** is the letterboxing/pillarboxing offset from `get_grid_offset`
+    pub fn tile_to_xyz(tile: TileTransform, (x_offset, y_offset): (f32, f32)) -> (f32, f32) {
+        let x = tile.x as f32 * TILE_WIDTH_HEIGHT as f32 + TILE_WIDTH + x_offset;
+        let y = (HEIGHT - tile.y) as f32 * TILE_WIDTH_HEIGHT as f32 - TILE_HEIGHT + y_offset;
         (x, y)
     }
 }
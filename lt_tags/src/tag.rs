@@ -4,6 +4,9 @@ use crate::trigger_type::TriggerType;
 pub enum Tag {
     Player(usize),
     Collision,
+    Pushable,
+    Hazard,
+    Floor,
     Trigger(TriggerType),
     Other,
 }
@@ -1,7 +1,13 @@
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum TriggerType {
     Door,
     Player(usize),
+    ///Opens every gate with the same link id while something is on it
+    PressurePlate(usize),
+    ///Toggles every gate with the same link id when a tribe steps onto it
+    Switch(usize),
+    ///An open gate - closed gates are normal colliders
+    Gate(usize),
 }
 impl TriggerType {
     #[allow(dead_code)]
@@ -9,6 +15,23 @@ impl TriggerType {
         match self {
             Self::Door => 10,
             Self::Player(u) => *u,
+            Self::PressurePlate(link) | Self::Switch(link) | Self::Gate(link) => 100 + *link,
+        }
+    }
+    ///Gets the link id for pressure plates, switches and gates
+    pub fn link(&self) -> Option<usize> {
+        match self {
+            Self::PressurePlate(link) | Self::Switch(link) | Self::Gate(link) => Some(*link),
+            _ => None,
+        }
+    }
+    ///Gets a copy of this trigger with a different link id - triggers without one are unchanged
+    pub fn with_link(self, link: usize) -> Self {
+        match self {
+            Self::PressurePlate(_) => Self::PressurePlate(link),
+            Self::Switch(_) => Self::Switch(link),
+            Self::Gate(_) => Self::Gate(link),
+            _ => self,
         }
     }
     pub fn from_id(id: &usize) -> Self {